@@ -0,0 +1,265 @@
+use rustc_hash::FxHashMap as HashMap;
+use std::{
+    env, fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+/// How often `step` looks at the clock. Reading it on every step would
+/// dominate tight loops such as the day 17 interpreter.
+const CLOCK_INTERVAL: u64 = 1024;
+
+/// Item limit applied by `from_env` when `AOC_MAX_ITEMS` isn't set, so a
+/// runaway collection fails long before it exhausts memory.
+pub const DEFAULT_MAX_ITEMS: usize = 1 << 24;
+
+/// Limits on how much work a solver may do before giving up. The limits are
+/// cooperative: they're only enforced when the solver calls `step`, `check`
+/// or `check_items`.
+///
+/// A budget is shared by reference between everything a solver runs,
+/// including rayon workers, so all counters are atomic.
+#[derive(Debug, Default)]
+pub struct Budget {
+    max_steps: Option<u64>,
+    deadline: Option<(Instant, Duration)>,
+    max_items: Option<usize>,
+    item_limits: HashMap<String, usize>,
+    steps: AtomicU64,
+    cancelled: CancelToken,
+}
+
+/// A handle that can stop a running solver from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BudgetExceeded {
+    Steps(u64),
+    Time(Duration),
+    Items {
+        function: &'static str,
+        size: usize,
+        limit: usize,
+    },
+    Cancelled,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetExceeded::Steps(limit) => write!(f, "budget exceeded: more than {limit} steps"),
+            BudgetExceeded::Time(limit) => {
+                write!(f, "budget exceeded: ran for more than {limit:?}")
+            }
+            BudgetExceeded::Items {
+                function,
+                size,
+                limit,
+            } => write!(
+                f,
+                "budget exceeded: {function} holds {size} items, over its limit of {limit}"
+            ),
+            BudgetExceeded::Cancelled => write!(f, "budget exceeded: cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Reads limits from `AOC_MAX_STEPS`, `AOC_TIMEOUT_MS` and `AOC_MAX_ITEMS`,
+    /// so whatever runs the days can apply its own per-day limits. Item limits
+    /// for a single function can be set with e.g. `AOC_MAX_ITEMS_COUNT_TRAILS`.
+    pub fn from_env() -> Self {
+        fn var<T: std::str::FromStr>(name: &str) -> Option<T> {
+            env::var(name).ok().and_then(|value| value.parse().ok())
+        }
+
+        let mut budget = Self::unlimited();
+        if let Some(steps) = var("AOC_MAX_STEPS") {
+            budget = budget.with_max_steps(steps);
+        }
+        if let Some(ms) = var("AOC_TIMEOUT_MS") {
+            budget = budget.with_timeout(Duration::from_millis(ms));
+        }
+        budget = budget.with_max_items(var("AOC_MAX_ITEMS").unwrap_or(DEFAULT_MAX_ITEMS));
+        for (name, value) in env::vars() {
+            if let (Some(function), Ok(limit)) =
+                (name.strip_prefix("AOC_MAX_ITEMS_"), value.parse())
+            {
+                budget = budget.with_item_limit(function.to_lowercase(), limit);
+            }
+        }
+        budget
+    }
+
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// The clock starts when this is called, not when solving starts.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some((Instant::now() + timeout, timeout));
+        self
+    }
+
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    /// Overrides the item limit for collections checked under `function`.
+    pub fn with_item_limit(mut self, function: impl Into<String>, limit: usize) -> Self {
+        self.item_limits.insert(function.into(), limit);
+        self
+    }
+
+    pub fn cancel_token(&self) -> CancelToken {
+        self.cancelled.clone()
+    }
+
+    /// Records one unit of work, failing if any limit has run out.
+    pub fn step(&self) -> Result<(), BudgetExceeded> {
+        let steps = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(max_steps) = self.max_steps {
+            if steps > max_steps {
+                return Err(BudgetExceeded::Steps(max_steps));
+            }
+        }
+        if steps.is_multiple_of(CLOCK_INTERVAL) {
+            self.check()?;
+        }
+        Ok(())
+    }
+
+    /// Checks cancellation and the deadline without recording any work.
+    pub fn check(&self) -> Result<(), BudgetExceeded> {
+        if self.cancelled.is_cancelled() {
+            return Err(BudgetExceeded::Cancelled);
+        }
+        if let Some((deadline, timeout)) = self.deadline {
+            if Instant::now() > deadline {
+                return Err(BudgetExceeded::Time(timeout));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if a working collection in `function` has grown past its limit.
+    pub fn check_items(&self, function: &'static str, size: usize) -> Result<(), BudgetExceeded> {
+        match self.item_limits.get(function).copied().or(self.max_items) {
+            Some(limit) if size > limit => Err(BudgetExceeded::Items {
+                function,
+                size,
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn steps_taken(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_steps() {
+        let budget = Budget::unlimited().with_max_steps(3);
+        for _ in 0..3 {
+            budget.step().unwrap();
+        }
+        assert_eq!(budget.step(), Err(BudgetExceeded::Steps(3)));
+        assert_eq!(budget.steps_taken(), 4);
+
+        let budget = Budget::unlimited();
+        for _ in 0..10_000 {
+            budget.step().unwrap();
+        }
+    }
+
+    #[test]
+    fn limits_time() {
+        let timeout = Duration::from_millis(1);
+        let budget = Budget::unlimited().with_timeout(timeout);
+        budget.check().unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(budget.check(), Err(BudgetExceeded::Time(timeout)));
+        // Steps only look at the clock every `CLOCK_INTERVAL` of them.
+        for _ in 1..CLOCK_INTERVAL {
+            budget.step().unwrap();
+        }
+        assert_eq!(budget.step(), Err(BudgetExceeded::Time(timeout)));
+    }
+
+    #[test]
+    fn cancels() {
+        let budget = Budget::unlimited();
+        budget.check().unwrap();
+        budget.cancel_token().cancel();
+        assert_eq!(budget.check(), Err(BudgetExceeded::Cancelled));
+    }
+
+    #[test]
+    fn limits_items() {
+        let budget = Budget::unlimited();
+        budget.check_items("anything", usize::MAX).unwrap();
+
+        let budget = Budget::unlimited()
+            .with_max_items(10)
+            .with_item_limit("big", 100)
+            .with_item_limit("small", 1);
+        budget.check_items("other", 10).unwrap();
+        assert_eq!(
+            budget.check_items("other", 11),
+            Err(BudgetExceeded::Items {
+                function: "other",
+                size: 11,
+                limit: 10
+            })
+        );
+        budget.check_items("big", 100).unwrap();
+        assert!(budget.check_items("big", 101).is_err());
+        assert!(budget.check_items("small", 2).is_err());
+    }
+
+    #[test]
+    fn reads_limits_from_env() {
+        // Nothing else reads this variable, so setting it can't disturb
+        // tests running alongside.
+        env::set_var("AOC_MAX_ITEMS_BUDGET_TEST", "5");
+        let budget = Budget::from_env();
+        env::remove_var("AOC_MAX_ITEMS_BUDGET_TEST");
+        budget.check_items("budget_test", 5).unwrap();
+        assert_eq!(
+            budget.check_items("budget_test", 6),
+            Err(BudgetExceeded::Items {
+                function: "budget_test",
+                size: 6,
+                limit: 5
+            })
+        );
+    }
+}
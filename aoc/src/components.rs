@@ -0,0 +1,222 @@
+use crate::grid::{Grid, Pos};
+
+/// A disjoint-set forest over the elements `0..len`, for merging elements into
+/// groups and asking which group an element is in.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    /// Puts every element in a set of its own.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            sets: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// How many disjoint sets there are.
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    /// The representative element of the set containing `x`.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parents[x] != x {
+            self.parents[x] = self.parents[self.parents[x]];
+            x = self.parents[x];
+        }
+        x
+    }
+
+    /// Merges the sets containing `a` and `b`, returning whether they were
+    /// separate.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The size of the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+}
+
+/// Which cells count as touching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells sharing an edge.
+    Four,
+    /// Cells sharing an edge or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    /// The steps to every touching cell.
+    pub fn steps(self) -> &'static [Pos] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (1, 0), (0, -1), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// A grid's cells split into connected components.
+#[derive(Debug, Clone)]
+pub struct Components {
+    /// Each cell's component, numbered from zero in the order their first
+    /// cells appear row by row.
+    pub labels: Grid<usize>,
+    /// The number of cells in each component.
+    pub sizes: Vec<usize>,
+}
+
+impl Components {
+    pub fn len(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sizes.is_empty()
+    }
+}
+
+/// Labels the connected components of `grid`, where touching cells are in the
+/// same component if `connected` holds for them. Every cell gets a label, so
+/// cells that aren't connected to anything are components of their own.
+/// `connected` should be symmetric.
+pub fn label<T>(
+    grid: &Grid<T>,
+    connectivity: Connectivity,
+    connected: impl Fn(&T, &T) -> bool,
+) -> Components {
+    let width = grid.width();
+    let index = |pos: Pos| (pos.1 * width + pos.0) as usize;
+    let mut sets = UnionFind::new((grid.width() * grid.height()) as usize);
+    for (pos, cell) in grid.iter() {
+        // Only look ahead, as the cells behind have already looked here.
+        for step in connectivity
+            .steps()
+            .iter()
+            .filter(|step| (step.1, step.0) > (0, 0))
+        {
+            let next = (pos.0 + step.0, pos.1 + step.1);
+            if grid.get(next).is_some_and(|other| connected(cell, other)) {
+                sets.union(index(pos), index(next));
+            }
+        }
+    }
+
+    let mut roots = vec![usize::MAX; sets.len()];
+    let mut sizes = Vec::new();
+    let labels = Grid::new(
+        grid.width(),
+        grid.height(),
+        (0..sets.len())
+            .map(|i| {
+                let root = sets.find(i);
+                if roots[root] == usize::MAX {
+                    roots[root] = sizes.len();
+                    sizes.push(sets.size(root));
+                }
+                roots[root]
+            })
+            .collect(),
+    )
+    .unwrap();
+    Components { labels, sizes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Grid<char> {
+        Grid::parse(input, |_, c| Ok(c)).unwrap()
+    }
+
+    fn labels(components: &Components) -> String {
+        let mut text = String::new();
+        for y in 0..components.labels.height() {
+            for x in 0..components.labels.width() {
+                let label = components.labels.get((x, y)).unwrap();
+                text.push(char::from_digit(*label as u32, 36).unwrap());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    #[test]
+    fn union_find_merges_sets() {
+        let mut sets = UnionFind::new(5);
+        assert_eq!(sets.sets(), 5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(1, 0));
+        assert!(sets.union(1, 4));
+        assert_eq!(sets.sets(), 2);
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(0, 2));
+        assert_eq!(sets.size(4), 4);
+        assert_eq!(sets.size(2), 1);
+    }
+
+    #[test]
+    fn four_connectivity_splits_diagonals() {
+        let grid = parse("#..#\n.#.#\n..##\n");
+        let components = label(&grid, Connectivity::Four, |a, b| a == b);
+        assert_eq!(labels(&components), "0112\n3412\n3322\n");
+        assert_eq!(components.sizes, vec![1, 3, 4, 3, 1]);
+    }
+
+    #[test]
+    fn eight_connectivity_joins_both_diagonals() {
+        // The right-hand column is only joined to the other #s by the step
+        // down and to the left from (3, 1), which the look-ahead must cover.
+        let grid = parse("#..#\n.#.#\n..#.\n");
+        let components = label(&grid, Connectivity::Eight, |a, b| a == b);
+        assert_eq!(labels(&components), "0110\n1010\n1101\n");
+        assert_eq!(components.sizes, vec![5, 7]);
+
+        let grid = parse("..#\n.#.\n#..\n");
+        let components = label(&grid, Connectivity::Eight, |a, b| a == b);
+        assert_eq!(labels(&components), "001\n010\n100\n");
+        assert_eq!(components.len(), 2);
+    }
+}
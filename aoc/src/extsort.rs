@@ -0,0 +1,379 @@
+use anyhow::{ensure, Context, Result};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    iter,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The most runs merged at once, which bounds the open files and read
+/// buffers. More runs than this are merged in several passes.
+const MAX_MERGE: usize = 16;
+
+/// Sorts `values` with a least significant digit radix sort, a byte at a time,
+/// using `scratch` as the second buffer. Bytes that every value shares are
+/// skipped, so small numbers only take a pass or two.
+pub fn radix_sort(values: &mut Vec<u64>, scratch: &mut Vec<u64>) {
+    scratch.clear();
+    scratch.resize(values.len(), 0);
+    for shift in (0..64).step_by(8) {
+        let mut offsets = [0; 256];
+        for value in values.iter() {
+            offsets[(value >> shift) as usize & 0xff] += 1;
+        }
+        if offsets.contains(&values.len()) {
+            continue;
+        }
+        let mut next = 0;
+        for offset in &mut offsets {
+            (*offset, next) = (next, next + *offset);
+        }
+        for &value in values.iter() {
+            let digit = (value >> shift) as usize & 0xff;
+            scratch[offsets[digit]] = value;
+            offsets[digit] += 1;
+        }
+        std::mem::swap(values, scratch);
+    }
+}
+
+/// A file in the system's temporary directory that's deleted when dropped.
+#[derive(Debug)]
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn create() -> Result<(Self, File)> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "aoc-sort-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        Ok((Self { path }, file))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Sorts more numbers than fit in memory. Numbers are buffered until the
+/// buffer reaches its limit, then radix sorted and written out to a temporary
+/// file as a sorted run. Finishing merges the runs back together.
+#[derive(Debug)]
+pub struct ExternalSorter {
+    capacity: usize,
+    buffer: Vec<u64>,
+    scratch: Vec<u64>,
+    runs: Vec<TempFile>,
+    len: usize,
+}
+
+impl ExternalSorter {
+    /// A sorter holding at most about `memory` bytes of numbers at once,
+    /// counting the radix sort's second buffer.
+    pub fn new(memory: usize) -> Self {
+        Self {
+            capacity: (memory / (2 * size_of::<u64>())).max(1),
+            buffer: Vec::new(),
+            scratch: Vec::new(),
+            runs: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: u64) -> Result<()> {
+        if self.buffer.len() == self.capacity {
+            self.spill()?;
+        }
+        self.buffer.push(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    fn spill(&mut self) -> Result<()> {
+        radix_sort(&mut self.buffer, &mut self.scratch);
+        let run = write_run(self.buffer.drain(..).map(Ok))?;
+        self.runs.push(run);
+        Ok(())
+    }
+
+    /// How many numbers have been pushed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How many runs have been written to disk so far.
+    pub fn spilled_runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Sorts whatever is still buffered. If nothing was spilled the numbers
+    /// stay in memory, otherwise the rest is spilled too.
+    pub fn finish(mut self) -> Result<Sorted> {
+        if self.runs.is_empty() {
+            radix_sort(&mut self.buffer, &mut self.scratch);
+            return Ok(Sorted {
+                memory: self.buffer,
+                runs: Vec::new(),
+                len: self.len,
+            });
+        }
+        if !self.buffer.is_empty() {
+            self.spill()?;
+        }
+        while self.runs.len() > MAX_MERGE {
+            self.runs = self
+                .runs
+                .chunks(MAX_MERGE)
+                .map(|runs| write_run(merge(runs)?))
+                .collect::<Result<_>>()?;
+        }
+        Ok(Sorted {
+            memory: Vec::new(),
+            runs: self.runs,
+            len: self.len,
+        })
+    }
+}
+
+/// The numbers from an `ExternalSorter`, which can be read back in order as
+/// many times as needed.
+#[derive(Debug)]
+pub struct Sorted {
+    memory: Vec<u64>,
+    runs: Vec<TempFile>,
+    len: usize,
+}
+
+impl Sorted {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The numbers in ascending order. Runs on disk are merged as they're
+    /// read, with one buffered reader per run.
+    pub fn iter(&self) -> Result<Box<dyn Iterator<Item = Result<u64>> + '_>> {
+        if self.runs.is_empty() {
+            return Ok(Box::new(self.memory.iter().map(|&value| Ok(value))));
+        }
+        Ok(Box::new(merge(&self.runs)?))
+    }
+}
+
+/// Writes sorted numbers to a new run.
+fn write_run(values: impl Iterator<Item = Result<u64>>) -> Result<TempFile> {
+    let (run, file) = TempFile::create()?;
+    let mut writer = BufWriter::new(file);
+    for value in values {
+        writer.write_all(&value?.to_le_bytes())?;
+    }
+    writer
+        .flush()
+        .with_context(|| format!("Failed to write {}", run.path.display()))?;
+    Ok(run)
+}
+
+/// Merges sorted runs as they're read. A run that can't be read ends the
+/// merge with an error, after the value already taken from it.
+fn merge(runs: &[TempFile]) -> Result<impl Iterator<Item = Result<u64>>> {
+    let mut readers = runs
+        .iter()
+        .map(|run| {
+            let reader = File::open(&run.path)
+                .map(BufReader::new)
+                .with_context(|| format!("Failed to open {}", run.path.display()))?;
+            Ok((reader, run.path.clone()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut heap = BinaryHeap::new();
+    for (i, (reader, path)) in readers.iter_mut().enumerate() {
+        if let Some(value) = read_value(reader, path)? {
+            heap.push(Reverse((value, i)));
+        }
+    }
+    let mut error = None;
+    let mut failed = false;
+    Ok(iter::from_fn(move || {
+        if let Some(e) = error.take() {
+            failed = true;
+            return Some(Err(e));
+        }
+        if failed {
+            return None;
+        }
+        let Reverse((value, i)) = heap.pop()?;
+        let (reader, path) = &mut readers[i];
+        match read_value(reader, path) {
+            Ok(Some(next)) => heap.push(Reverse((next, i))),
+            Ok(None) => {}
+            Err(e) => error = Some(e),
+        }
+        Some(Ok(value))
+    }))
+}
+
+/// Reads the next number from a run, or `None` at the end of it. A run that
+/// ends part-way through a number has been cut short, which is an error.
+fn read_value(reader: &mut impl Read, path: &Path) -> Result<Option<u64>> {
+    let mut bytes = [0; size_of::<u64>()];
+    let mut filled = 0;
+    while filled < bytes.len() {
+        match reader.read(&mut bytes[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+    if filled == 0 {
+        return Ok(None);
+    }
+    ensure!(
+        filled == bytes.len(),
+        "{} ends with {filled} bytes of a number, so it was cut short",
+        path.display()
+    );
+    Ok(Some(u64::from_le_bytes(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Rng;
+
+    fn random_values(n: usize, seed: u64) -> Vec<u64> {
+        let mut rng = Rng::new(seed);
+        (0..n).map(|_| rng.next_u64()).collect()
+    }
+
+    #[test]
+    fn radix_sort_matches_sort() {
+        let mut scratch = Vec::new();
+        for mut values in [
+            Vec::new(),
+            vec![7],
+            vec![3, 1, 2, 1],
+            random_values(1000, 1),
+            random_values(1000, 2)
+                .into_iter()
+                .map(|v| v % 300)
+                .collect(),
+            vec![u64::MAX, 0, 1 << 63, u64::MAX, 255, 256],
+        ] {
+            let mut expected = values.clone();
+            expected.sort_unstable();
+            radix_sort(&mut values, &mut scratch);
+            assert_eq!(values, expected);
+        }
+    }
+
+    fn sort_externally(values: &[u64], memory: usize) -> (usize, Vec<u64>) {
+        let mut sorter = ExternalSorter::new(memory);
+        for &value in values {
+            sorter.push(value).unwrap();
+        }
+        assert_eq!(sorter.len(), values.len());
+        let spilled = sorter.spilled_runs();
+        let sorted = sorter.finish().unwrap();
+        assert!(sorted.runs.len() <= MAX_MERGE);
+        // Reading back twice gives the same numbers.
+        let first = sorted.iter().unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let second = sorted.iter().unwrap().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(first, second);
+        (spilled, first)
+    }
+
+    #[test]
+    fn external_sort_in_memory() {
+        let values = random_values(100, 3);
+        let mut expected = values.clone();
+        expected.sort_unstable();
+        assert_eq!(sort_externally(&values, 1 << 20), (0, expected));
+        assert_eq!(sort_externally(&[], 16), (0, Vec::new()));
+    }
+
+    #[test]
+    fn external_sort_spills_and_merges_in_passes() {
+        let values = random_values(1000, 4);
+        let mut expected = values.clone();
+        expected.sort_unstable();
+        // 16 bytes holds a single number beside its scratch space, so every
+        // number gets a run of its own and merging takes three passes.
+        let (spilled, sorted) = sort_externally(&values, 16);
+        assert_eq!(spilled, 999);
+        assert_eq!(sorted, expected);
+        // Runs of 63 leave exactly `MAX_MERGE` runs, merged in one pass.
+        let (spilled, sorted) = sort_externally(&values, 63 * 16);
+        assert_eq!(spilled, MAX_MERGE - 1);
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn reads_values_split_across_reads() {
+        let path = Path::new("run");
+        let bytes = 0x0807060504030201u64.to_le_bytes();
+        let mut reader = bytes[..3].chain(&bytes[3..]);
+        assert_eq!(
+            read_value(&mut reader, path).unwrap(),
+            Some(0x0807060504030201)
+        );
+        assert_eq!(read_value(&mut reader, path).unwrap(), None);
+        let error = read_value(&mut &bytes[..5], path).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "run ends with 5 bytes of a number, so it was cut short"
+        );
+    }
+
+    #[test]
+    fn merge_stops_at_a_truncated_run() {
+        let whole = write_run([1, 4].into_iter().map(Ok)).unwrap();
+        let truncated = write_run([2, 3].into_iter().map(Ok)).unwrap();
+        OpenOptions::new()
+            .append(true)
+            .open(&truncated.path)
+            .unwrap()
+            .write_all(&[0; 3])
+            .unwrap();
+        let runs = [whole, truncated];
+        let mut merged = merge(&runs).unwrap();
+        // The error comes from reading on past 3, so 3 still comes out, but 4
+        // doesn't once the error has been returned.
+        for expected in [1, 2, 3] {
+            assert_eq!(merged.next().unwrap().unwrap(), expected);
+        }
+        assert!(merged.next().unwrap().is_err());
+        assert!(merged.next().is_none());
+        assert!(merged.next().is_none());
+    }
+
+    #[test]
+    fn temp_files_are_removed() {
+        let (run, _) = TempFile::create().unwrap();
+        let path = run.path.clone();
+        assert!(path.exists());
+        drop(run);
+        assert!(!path.exists());
+    }
+}
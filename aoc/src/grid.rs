@@ -0,0 +1,236 @@
+use anyhow::{ensure, Context, Result};
+
+pub type Pos = (isize, isize);
+
+/// Left, right, up and down, as (x, y) steps.
+pub const DIRECTIONS: [Pos; 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// A rectangular grid stored row by row, indexed by (x, y) positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: isize,
+    height: isize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: isize, height: isize, cells: Vec<T>) -> Result<Self> {
+        ensure!(
+            width >= 0 && height >= 0 && cells.len() == (width * height) as usize,
+            "{} cells don't make a {width}x{height} grid",
+            cells.len()
+        );
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn filled(width: isize, height: isize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; (width * height) as usize],
+        }
+    }
+
+    /// Parses one row per line, turning each character into a cell with `f`.
+    pub fn parse(input: &str, mut f: impl FnMut(Pos, char) -> Result<T>) -> Result<Self> {
+        let width = input.lines().next().context("Empty input")?.chars().count() as isize;
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            ensure!(
+                line.chars().count() as isize == width,
+                "Line {} has a different width to the first",
+                y + 1
+            );
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f((x as isize, y as isize), c)?);
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> isize {
+        self.width
+    }
+
+    pub fn height(&self) -> isize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        (0..self.width).contains(&pos.0) && (0..self.height).contains(&pos.1)
+    }
+
+    fn index(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos)
+            .then(|| (pos.1 * self.width + pos.0) as usize)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The in-bounds positions one step from `pos` in each of `DIRECTIONS`.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS
+            .into_iter()
+            .map(move |dir| (pos.0 + dir.0, pos.1 + dir.1))
+            .filter(|&next| self.contains(next))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Prints one row per line, turning each cell into a character with `f`.
+    pub fn to_text(&self, f: impl Fn(&T) -> char) -> String {
+        let mut text = String::new();
+        for row in self.cells.chunks(self.width.max(1) as usize) {
+            for cell in row {
+                text.push(f(cell));
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// A grid whose edges wrap around, so that every position is on it: stepping
+/// off one side comes back on the opposite side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Torus<T> {
+    grid: Grid<T>,
+}
+
+impl<T> Torus<T> {
+    pub fn new(grid: Grid<T>) -> Result<Self> {
+        ensure!(
+            grid.width > 0 && grid.height > 0,
+            "A {}x{} grid can't wrap",
+            grid.width,
+            grid.height
+        );
+        Ok(Self { grid })
+    }
+
+    pub fn parse(input: &str, f: impl FnMut(Pos, char) -> Result<T>) -> Result<Self> {
+        Self::new(Grid::parse(input, f)?)
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// The position on the grid that `pos` wraps around to.
+    pub fn wrap(&self, pos: Pos) -> Pos {
+        (
+            pos.0.rem_euclid(self.grid.width),
+            pos.1.rem_euclid(self.grid.height),
+        )
+    }
+
+    pub fn get(&self, pos: Pos) -> &T {
+        self.grid.get(self.wrap(pos)).unwrap()
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> &mut T {
+        let pos = self.wrap(pos);
+        self.grid.get_mut(pos).unwrap()
+    }
+
+    /// The positions one step from `pos` in each of `DIRECTIONS`, wrapped.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS
+            .into_iter()
+            .map(move |dir| self.wrap((pos.0 + dir.0, pos.1 + dir.1)))
+    }
+
+    pub fn to_text(&self, f: impl Fn(&T) -> char) -> String {
+        self.grid.to_text(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn torus() -> Torus<char> {
+        Torus::parse("abc\ndef", |_, c| Ok(c)).unwrap()
+    }
+
+    #[test]
+    fn torus_wraps_every_position() {
+        let torus = torus();
+        assert_eq!(torus.wrap((1, 1)), (1, 1));
+        assert_eq!(torus.wrap((3, 2)), (0, 0));
+        assert_eq!(torus.wrap((-1, -1)), (2, 1));
+        assert_eq!(torus.wrap((-3, -2)), (0, 0));
+        assert_eq!(torus.wrap((-7, 5)), (2, 1));
+        assert_eq!(torus.wrap((isize::MIN, 0)), (1, 0));
+        assert_eq!(*torus.get((-1, 0)), 'c');
+        assert_eq!(*torus.get((4, -3)), 'e');
+    }
+
+    #[test]
+    fn torus_neighbours_wrap() {
+        let torus = torus();
+        assert_eq!(
+            torus.neighbours((0, 0)).collect::<Vec<_>>(),
+            [(2, 0), (1, 0), (0, 1), (0, 1)]
+        );
+        assert_eq!(
+            torus.neighbours((2, 1)).collect::<Vec<_>>(),
+            [(1, 1), (0, 1), (2, 0), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn torus_writes_through_wrapping() {
+        let mut torus = torus();
+        *torus.get_mut((-1, -1)) = 'x';
+        *torus.get_mut((3, 0)) = 'y';
+        assert_eq!(torus.to_text(|&c| c), "ybc\ndex\n");
+        assert_eq!(torus.into_grid().get((2, 1)), Some(&'x'));
+    }
+
+    #[test]
+    fn torus_needs_cells() {
+        assert!(Torus::new(Grid::<u8>::filled(0, 3, 0)).is_err());
+        assert!(Torus::new(Grid::<u8>::filled(3, 0, 0)).is_err());
+        assert!(Torus::new(Grid::filled(1, 1, 0)).is_ok());
+    }
+}
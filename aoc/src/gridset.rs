@@ -0,0 +1,369 @@
+use crate::grid::{Grid, Pos, DIRECTIONS};
+
+/// A fixed number of bits, packed into words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bits {
+    words: Vec<u64>,
+}
+
+impl Bits {
+    fn new(len: usize) -> Self {
+        Self {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    fn get(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Sets bit `i` to `value`, returning whether it changed.
+    fn set(&mut self, i: usize, value: bool) -> bool {
+        let word = &mut self.words[i / 64];
+        let old = *word;
+        if value {
+            *word |= 1 << (i % 64);
+        } else {
+            *word &= !(1 << (i % 64));
+        }
+        *word != old
+    }
+
+    fn count(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    fn combine(&mut self, other: &Bits, f: impl Fn(u64, u64) -> u64) {
+        assert_eq!(
+            self.words.len(),
+            other.words.len(),
+            "Sets cover different grids"
+        );
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word = f(*word, *other);
+        }
+    }
+
+    fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// A set of positions in a `width` by `height` grid, one bit per cell. Much
+/// faster than a hash set for visited cells. Positions outside the grid are
+/// never in the set, and inserting one panics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridSet {
+    width: isize,
+    height: isize,
+    bits: Bits,
+}
+
+impl GridSet {
+    pub fn new(width: isize, height: isize) -> Self {
+        Self {
+            width,
+            height,
+            bits: Bits::new((width * height) as usize),
+        }
+    }
+
+    /// An empty set covering the same positions as `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    fn index(&self, pos: Pos) -> Option<usize> {
+        ((0..self.width).contains(&pos.0) && (0..self.height).contains(&pos.1))
+            .then(|| (pos.1 * self.width + pos.0) as usize)
+    }
+
+    fn checked_index(&self, pos: Pos) -> usize {
+        self.index(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the {}x{} grid", self.width, self.height))
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.index(pos).is_some_and(|i| self.bits.get(i))
+    }
+
+    /// Adds `pos`, returning whether it wasn't already in the set.
+    pub fn insert(&mut self, pos: Pos) -> bool {
+        let i = self.checked_index(pos);
+        self.bits.set(i, true)
+    }
+
+    /// Removes `pos`, returning whether it was in the set.
+    pub fn remove(&mut self, pos: Pos) -> bool {
+        self.index(pos).is_some_and(|i| self.bits.set(i, false))
+    }
+
+    /// The number of positions in the set.
+    pub fn len(&self) -> usize {
+        self.bits.count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// The positions in the set, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.bits
+            .ones()
+            .map(|i| (i as isize % self.width, i as isize / self.width))
+    }
+
+    /// Adds every position in `other`, which must cover the same grid.
+    pub fn union_with(&mut self, other: &GridSet) {
+        self.bits.combine(&other.bits, |a, b| a | b);
+    }
+
+    /// Keeps only the positions also in `other`, which must cover the same
+    /// grid.
+    pub fn intersect_with(&mut self, other: &GridSet) {
+        self.bits.combine(&other.bits, |a, b| a & b);
+    }
+
+    /// Removes every position in `other`, which must cover the same grid.
+    pub fn difference_with(&mut self, other: &GridSet) {
+        self.bits.combine(&other.bits, |a, b| a & !b);
+    }
+
+    pub fn is_subset(&self, other: &GridSet) -> bool {
+        let mut difference = self.clone();
+        difference.difference_with(other);
+        difference.is_empty()
+    }
+}
+
+impl Extend<Pos> for GridSet {
+    fn extend<I: IntoIterator<Item = Pos>>(&mut self, iter: I) {
+        for pos in iter {
+            self.insert(pos);
+        }
+    }
+}
+
+/// A set of (position, direction) pairs, such as a guard's position and
+/// facing, with one bit for each of `DIRECTIONS` at every cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectionalGridSet {
+    /// A grid set with four bits per cell rather than one, for its bounds
+    /// checks.
+    cells: GridSet,
+}
+
+fn direction_index(direction: Pos) -> usize {
+    DIRECTIONS
+        .iter()
+        .position(|&d| d == direction)
+        .unwrap_or_else(|| panic!("{direction:?} isn't one of the four directions"))
+}
+
+impl DirectionalGridSet {
+    pub fn new(width: isize, height: isize) -> Self {
+        Self {
+            cells: GridSet {
+                width,
+                height,
+                bits: Bits::new((width * height) as usize * DIRECTIONS.len()),
+            },
+        }
+    }
+
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    fn bit(&self, cell: usize, direction: Pos) -> usize {
+        cell * DIRECTIONS.len() + direction_index(direction)
+    }
+
+    pub fn contains(&self, pos: Pos, direction: Pos) -> bool {
+        self.cells
+            .index(pos)
+            .is_some_and(|cell| self.cells.bits.get(self.bit(cell, direction)))
+    }
+
+    /// Adds `(pos, direction)`, returning whether it wasn't already in the
+    /// set.
+    pub fn insert(&mut self, pos: Pos, direction: Pos) -> bool {
+        let i = self.bit(self.cells.checked_index(pos), direction);
+        self.cells.bits.set(i, true)
+    }
+
+    /// Removes `(pos, direction)`, returning whether it was in the set.
+    pub fn remove(&mut self, pos: Pos, direction: Pos) -> bool {
+        self.cells.index(pos).is_some_and(|cell| {
+            let i = self.bit(cell, direction);
+            self.cells.bits.set(i, false)
+        })
+    }
+
+    /// The number of (position, direction) pairs in the set.
+    pub fn len(&self) -> usize {
+        self.cells.bits.count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+        let width = self.cells.width;
+        self.cells.bits.ones().map(move |i| {
+            let cell = (i / DIRECTIONS.len()) as isize;
+            (
+                (cell % width, cell / width),
+                DIRECTIONS[i % DIRECTIONS.len()],
+            )
+        })
+    }
+
+    /// The positions in the set in any direction.
+    pub fn positions(&self) -> GridSet {
+        let mut positions = GridSet::new(self.cells.width, self.cells.height);
+        positions.extend(self.iter().map(|(pos, _)| pos));
+        positions
+    }
+
+    pub fn union_with(&mut self, other: &DirectionalGridSet) {
+        self.cells.union_with(&other.cells);
+    }
+
+    pub fn intersect_with(&mut self, other: &DirectionalGridSet) {
+        self.cells.intersect_with(&other.cells);
+    }
+
+    pub fn difference_with(&mut self, other: &DirectionalGridSet) {
+        self.cells.difference_with(&other.cells);
+    }
+
+    pub fn is_subset(&self, other: &DirectionalGridSet) -> bool {
+        self.cells.is_subset(&other.cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_cross_word_boundaries() {
+        // 13x11 = 143 cells, so the set spans three words with the last one
+        // partly used.
+        let mut set = GridSet::new(13, 11);
+        let edges = [(0, 0), (11, 4), (12, 4), (0, 5), (11, 9), (12, 9), (12, 10)];
+        for pos in edges {
+            assert!(set.insert(pos));
+        }
+        assert!(!set.insert((12, 4)));
+        assert_eq!(set.len(), edges.len());
+        for pos in edges {
+            assert!(set.contains(pos), "{pos:?}");
+        }
+        assert!(!set.contains((10, 4)));
+        assert!(!set.contains((13, 0)));
+        assert!(!set.contains((-1, 0)));
+
+        assert!(set.remove((0, 5)));
+        assert!(!set.remove((0, 5)));
+        assert_eq!(set.len(), edges.len() - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 13x11 grid")]
+    fn inserting_outside_panics() {
+        GridSet::new(13, 11).insert((13, 0));
+    }
+
+    #[test]
+    fn iter_goes_row_by_row() {
+        let mut set = GridSet::new(70, 3);
+        set.extend([(5, 2), (69, 0), (0, 1), (1, 0), (64, 0)]);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [(1, 0), (64, 0), (69, 0), (0, 1), (5, 2)]
+        );
+    }
+
+    #[test]
+    fn set_operations() {
+        let mut a = GridSet::new(65, 2);
+        a.extend([(0, 0), (64, 0), (0, 1)]);
+        let mut b = GridSet::new(65, 2);
+        b.extend([(64, 0), (64, 1)]);
+
+        let mut union = a.clone();
+        union.union_with(&b);
+        assert_eq!(union.len(), 4);
+        let mut intersection = a.clone();
+        intersection.intersect_with(&b);
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), [(64, 0)]);
+        let mut difference = a.clone();
+        difference.difference_with(&b);
+        assert_eq!(difference.iter().collect::<Vec<_>>(), [(0, 0), (0, 1)]);
+        assert!(intersection.is_subset(&a));
+        assert!(!a.is_subset(&b));
+    }
+
+    #[test]
+    fn directional_sets_track_each_direction() {
+        let mut set = DirectionalGridSet::new(17, 5);
+        let entries = [
+            ((16, 3), DIRECTIONS[0]),
+            ((16, 3), DIRECTIONS[3]),
+            ((0, 0), DIRECTIONS[2]),
+            ((5, 4), DIRECTIONS[1]),
+        ];
+        for (pos, direction) in entries {
+            assert!(set.insert(pos, direction));
+        }
+        assert!(!set.insert((16, 3), DIRECTIONS[0]));
+        assert!(!set.contains((16, 3), DIRECTIONS[1]));
+        assert_eq!(set.len(), 4);
+
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [
+                ((0, 0), DIRECTIONS[2]),
+                ((16, 3), DIRECTIONS[0]),
+                ((16, 3), DIRECTIONS[3]),
+                ((5, 4), DIRECTIONS[1]),
+            ]
+        );
+        assert_eq!(
+            set.positions().iter().collect::<Vec<_>>(),
+            [(0, 0), (16, 3), (5, 4)]
+        );
+
+        assert!(set.remove((16, 3), DIRECTIONS[0]));
+        assert!(set.contains((16, 3), DIRECTIONS[3]));
+        assert_eq!(set.len(), 3);
+    }
+}
@@ -0,0 +1,17 @@
+pub mod alloc;
+pub mod budget;
+pub mod components;
+pub mod extsort;
+pub mod grid;
+pub mod gridset;
+pub mod memo;
+pub mod num;
+pub mod par;
+pub mod parse;
+pub mod render;
+pub mod runner;
+pub mod search;
+pub mod sim;
+pub mod similarity;
+pub mod sparse;
+pub mod testing;
@@ -0,0 +1,238 @@
+use rustc_hash::FxHashMap as HashMap;
+use std::{
+    borrow::Borrow,
+    fmt::Display,
+    hash::Hash,
+    sync::atomic::{AtomicU64, Ordering::Relaxed},
+};
+
+/// How often a [`Memo`] has been looked up, and how full it is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+impl Display for MemoStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lookups = self.hits + self.misses;
+        let rate = if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 * 100.0 / lookups as f64
+        };
+        write!(
+            f,
+            "{} hits, {} misses ({rate:.1}% hit rate), {} entries",
+            self.hits, self.misses, self.entries
+        )
+    }
+}
+
+/// A cache of computed results, counting its hits and misses. Lookups only
+/// need a shared reference, so a memo can be read from parallel iterators.
+///
+/// A memo made with `with_limit` stops storing new results once it's full,
+/// which only costs recomputing them.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    entries: HashMap<K, V>,
+    limit: Option<usize>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::default(),
+            limit: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..Self::default()
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let value = self.entries.get(key).cloned();
+        match value {
+            Some(_) => self.hits.fetch_add(1, Relaxed),
+            None => self.misses.fetch_add(1, Relaxed),
+        };
+        value
+    }
+
+    /// Stores `value` for `key`, unless the memo is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.limit.is_some_and(|limit| self.entries.len() >= limit)
+            && !self.entries.contains_key(&key)
+        {
+            return;
+        }
+        self.entries.insert(key, value);
+    }
+
+    /// Returns the result for `key`, computing it with `f` if it isn't cached.
+    /// `f` is given the memo so it can look up or compute other results.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Memoises the recursive function `f`, which is given a callback to make
+    /// its recursive calls through, along with the key to compute.
+    pub fn recurse<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, &K) -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f(&mut |key| self.recurse(key, f), &key);
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Forgets every cached result and resets the hit and miss counts.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        *self.hits.get_mut() = 0;
+        *self.misses.get_mut() = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits.load(Relaxed),
+            misses: self.misses.load(Relaxed),
+            entries: self.entries.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(hits: u64, misses: u64, entries: usize) -> MemoStats {
+        MemoStats {
+            hits,
+            misses,
+            entries,
+        }
+    }
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.get(&1), None);
+        memo.insert(1, "one");
+        assert_eq!(memo.get(&1), Some("one"));
+        assert_eq!(memo.get(&1), Some("one"));
+        assert_eq!(memo.get(&2), None);
+        assert_eq!(memo.stats(), stats(2, 2, 1));
+        assert_eq!(
+            memo.stats().to_string(),
+            "2 hits, 2 misses (50.0% hit rate), 1 entries"
+        );
+        assert_eq!(
+            Memo::<u8, u8>::new().stats().to_string(),
+            "0 hits, 0 misses (0.0% hit rate), 0 entries"
+        );
+    }
+
+    #[test]
+    fn computes_missing_values_once() {
+        let mut memo = Memo::new();
+        let mut calls = 0;
+        for _ in 0..3 {
+            let value = memo.get_or_insert_with("key", |_| {
+                calls += 1;
+                42
+            });
+            assert_eq!(value, 42);
+        }
+        assert_eq!(calls, 1);
+        assert_eq!(memo.stats(), stats(2, 1, 1));
+    }
+
+    #[test]
+    fn stops_storing_once_full() {
+        let mut memo = Memo::with_limit(2);
+        for i in 0..5 {
+            memo.insert(i, i * 10);
+        }
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get(&4), None);
+        // Existing entries can still be updated.
+        memo.insert(0, 99);
+        assert_eq!(memo.get(&0), Some(99));
+        assert_eq!(memo.len(), 2);
+    }
+
+    #[test]
+    fn clear_resets_everything() {
+        let mut memo = Memo::new();
+        memo.insert(1, 1);
+        memo.get(&1);
+        memo.get(&2);
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), stats(0, 0, 0));
+        assert_eq!(memo.get(&1), None);
+    }
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.recurse(n, &|fib, &n| {
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1) + fib(n - 2)
+            }
+        })
+    }
+
+    #[test]
+    fn memoises_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2880067194370816120);
+        // Each of 0 to 90 misses once, when first needed. The second call
+        // made by each of 3 to 90 hits, while 2's is 0's first.
+        assert_eq!(memo.stats(), stats(88, 91, 91));
+        assert_eq!(fibonacci(&mut memo, 50), 12586269025);
+        assert_eq!(memo.stats(), stats(89, 91, 91));
+
+        // A full memo still gives the right answers, just more slowly.
+        let mut memo = Memo::with_limit(10);
+        assert_eq!(fibonacci(&mut memo, 30), 832040);
+        assert_eq!(memo.len(), 10);
+    }
+}
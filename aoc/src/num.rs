@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+
+pub fn checked_add(a: i128, b: i128) -> Result<i128> {
+    a.checked_add(b)
+        .with_context(|| format!("Overflow computing {a} + {b}"))
+}
+
+pub fn checked_sub(a: i128, b: i128) -> Result<i128> {
+    a.checked_sub(b)
+        .with_context(|| format!("Overflow computing {a} - {b}"))
+}
+
+pub fn checked_mul(a: i128, b: i128) -> Result<i128> {
+    a.checked_mul(b)
+        .with_context(|| format!("Overflow computing {a} * {b}"))
+}
+
+/// The determinant of the 2x2 matrix `[[a, b], [c, d]]`.
+pub fn determinant([[a, b], [c, d]]: [[i128; 2]; 2]) -> Result<i128> {
+    checked_sub(checked_mul(a, d)?, checked_mul(b, c)?)
+}
+
+/// `a / b`, if `b` divides `a` exactly.
+pub fn exact_div(a: i128, b: i128) -> Option<i128> {
+    (b != 0 && a % b == 0).then(|| a / b)
+}
+
+pub fn gcd(a: i128, b: i128) -> i128 {
+    extended_gcd(a, b).0
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the
+/// non-negative greatest common divisor of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Solves the congruences `x ≡ residue (mod modulus)`, returning the smallest
+/// non-negative solution and the modulus it repeats with, or `None` if the
+/// congruences contradict each other. The moduli must be positive, but
+/// needn't be coprime.
+pub fn crt(congruences: &[(i128, i128)]) -> Result<Option<(i128, i128)>> {
+    let mut solution = (0, 1);
+    for &(residue, modulus) in congruences {
+        let (r1, m1) = solution;
+        let r2 = residue.rem_euclid(modulus);
+        let g = gcd(m1, modulus);
+        let Some(diff) = exact_div(checked_sub(r2, r1)?, g) else {
+            return Ok(None);
+        };
+        let m2 = modulus / g;
+        let lcm = checked_mul(m1, m2)?;
+        // m1 / g is coprime with m2 once the common factor is divided out.
+        let inverse = mod_inverse(m1 / g, m2).unwrap();
+        let k = checked_mul(diff.rem_euclid(m2), inverse)?.rem_euclid(m2);
+        solution = (checked_add(r1, checked_mul(m1, k)?)?.rem_euclid(lcm), lcm);
+    }
+    Ok(Some(solution))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_gcd_finds_bezout_coefficients() {
+        for (a, b, expected) in [
+            (240, 46, 2),
+            (-12, 18, 6),
+            (7, 0, 7),
+            (0, -5, 5),
+            (17, 5, 1),
+        ] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, expected, "gcd({a}, {b})");
+            assert_eq!(a * x + b * y, g, "coefficients for ({a}, {b})");
+        }
+    }
+
+    #[test]
+    fn mod_inverse_only_exists_for_coprime_values() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn crt_solves_coprime_moduli() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]).unwrap(), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 101), (-2, 103)]).unwrap(), Some((5251, 10403)));
+        assert_eq!(crt(&[]).unwrap(), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_solves_consistent_non_coprime_moduli() {
+        // x ≡ 2 (mod 4) and x ≡ 4 (mod 6) agree modulo their common factor 2.
+        assert_eq!(crt(&[(2, 4), (4, 6)]).unwrap(), Some((10, 12)));
+        assert_eq!(crt(&[(3, 6), (3, 9), (1, 4)]).unwrap(), Some((21, 36)));
+    }
+
+    #[test]
+    fn crt_rejects_inconsistent_moduli() {
+        assert_eq!(crt(&[(1, 4), (2, 6)]).unwrap(), None);
+        assert_eq!(crt(&[(0, 2), (1, 4)]).unwrap(), None);
+    }
+
+    #[test]
+    fn crt_reports_overflow() {
+        let big = i128::MAX / 2 + 1;
+        assert!(crt(&[(0, big), (1, big - 1)]).is_err());
+    }
+}
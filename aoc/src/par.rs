@@ -0,0 +1,71 @@
+//! Iteration that runs on rayon when the `parallel` feature is enabled and
+//! serially otherwise. Solvers call `into_maybe_par_iter`/`maybe_par_iter` and
+//! the adaptors that rayon and std have in common, so both builds share one
+//! code path and produce the same answers.
+
+pub mod prelude {
+    #[cfg(not(feature = "parallel"))]
+    pub use super::MapInit;
+    pub use super::{IntoMaybeParIter, MaybeParIter};
+    #[cfg(feature = "parallel")]
+    pub use rayon::prelude::*;
+}
+
+pub trait IntoMaybeParIter {
+    type Iter;
+
+    fn into_maybe_par_iter(self) -> Self::Iter;
+}
+
+#[cfg(feature = "parallel")]
+impl<T: rayon::iter::IntoParallelIterator> IntoMaybeParIter for T {
+    type Iter = T::Iter;
+
+    fn into_maybe_par_iter(self) -> Self::Iter {
+        self.into_par_iter()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl<T: IntoIterator> IntoMaybeParIter for T {
+    type Iter = T::IntoIter;
+
+    fn into_maybe_par_iter(self) -> Self::Iter {
+        self.into_iter()
+    }
+}
+
+pub trait MaybeParIter<'a> {
+    type Iter;
+
+    fn maybe_par_iter(&'a self) -> Self::Iter;
+}
+
+impl<'a, T: 'a + ?Sized> MaybeParIter<'a> for T
+where
+    &'a T: IntoMaybeParIter,
+{
+    type Iter = <&'a T as IntoMaybeParIter>::Iter;
+
+    fn maybe_par_iter(&'a self) -> Self::Iter {
+        self.into_maybe_par_iter()
+    }
+}
+
+/// Rayon's `map_init` for serial iterators, so solvers can keep scratch state
+/// per worker in both builds. Serially there's one worker, so `init` runs
+/// once.
+#[cfg(not(feature = "parallel"))]
+pub trait MapInit: Iterator + Sized {
+    fn map_init<T, R>(
+        self,
+        init: impl FnOnce() -> T,
+        mut f: impl FnMut(&mut T, Self::Item) -> R,
+    ) -> impl Iterator<Item = R> {
+        let mut state = init();
+        self.map(move |item| f(&mut state, item))
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl<I: Iterator> MapInit for I {}
@@ -0,0 +1,133 @@
+use crate::{
+    grid::{Grid, Pos},
+    sparse::SparseGrid,
+};
+use anyhow::{Context, Result};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+pub const BLACK: Colour = Colour::new(0, 0, 0);
+pub const WHITE: Colour = Colour::new(255, 255, 255);
+pub const GREEN: Colour = Colour::new(0, 255, 0);
+pub const RED: Colour = Colour::new(255, 0, 0);
+pub const GREY: Colour = Colour::new(128, 128, 128);
+pub const BROWN: Colour = Colour::new(160, 96, 32);
+
+/// A distinct colour for each small index, for telling regions apart.
+pub fn palette(i: usize) -> Colour {
+    const COLOURS: [Colour; 8] = [
+        Colour::new(230, 25, 75),
+        Colour::new(60, 180, 75),
+        Colour::new(255, 225, 25),
+        Colour::new(0, 130, 200),
+        Colour::new(245, 130, 48),
+        Colour::new(145, 30, 180),
+        Colour::new(70, 240, 240),
+        Colour::new(240, 50, 230),
+    ];
+    COLOURS[i % COLOURS.len()]
+}
+
+/// An image with one `scale`-sized square per grid cell. Images can always be
+/// drawn, but saving them needs the `viz` feature.
+pub struct Image {
+    cells: Grid<Colour>,
+    #[cfg_attr(not(feature = "viz"), allow(dead_code))]
+    scale: usize,
+}
+
+impl Image {
+    pub fn new(width: isize, height: isize, scale: usize, background: Colour) -> Self {
+        Self {
+            cells: Grid::filled(width, height, background),
+            scale,
+        }
+    }
+
+    /// Colours the cell at `pos`, if it's in the image.
+    pub fn fill(&mut self, pos: Pos, colour: Colour) {
+        if let Some(cell) = self.cells.get_mut(pos) {
+            *cell = colour;
+        }
+    }
+
+    /// Encodes the image as a PNG.
+    #[cfg(feature = "viz")]
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let (width, height) = (
+            self.cells.width() as usize * self.scale,
+            self.cells.height() as usize * self.scale,
+        );
+        let pixels = (0..height * width)
+            .flat_map(|i| {
+                let pos = (
+                    (i % width / self.scale) as isize,
+                    (i / width / self.scale) as isize,
+                );
+                let Colour { r, g, b } = *self.cells.get(pos).unwrap();
+                [r, g, b]
+            })
+            .collect::<Vec<_>>();
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        writer.finish()?;
+        Ok(png)
+    }
+
+    #[cfg(not(feature = "viz"))]
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        anyhow::bail!("Saving images needs the viz feature")
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_png()?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Draws every cell that `colour` gives a colour to over a black background.
+pub fn draw_grid<T>(grid: &Grid<T>, scale: usize, colour: impl Fn(&T) -> Option<Colour>) -> Image {
+    let mut image = Image::new(grid.width(), grid.height(), scale, BLACK);
+    for (pos, cell) in grid.iter() {
+        if let Some(colour) = colour(cell) {
+            image.fill(pos, colour);
+        }
+    }
+    image
+}
+
+/// Draws a sparse grid's bounds like `draw_grid`, with the top left of the
+/// bounds in the top left of the image.
+pub fn draw_sparse<T>(
+    grid: &SparseGrid<T>,
+    scale: usize,
+    colour: impl Fn(&T) -> Option<Colour>,
+) -> Image {
+    let Some((min, max)) = grid.bounds() else {
+        return Image::new(0, 0, scale, BLACK);
+    };
+    let mut image = Image::new(max.0 - min.0 + 1, max.1 - min.1 + 1, scale, BLACK);
+    for (pos, cell) in grid.iter() {
+        if let Some(colour) = colour(cell) {
+            image.fill((pos.0 - min.0, pos.1 - min.1), colour);
+        }
+    }
+    image
+}
@@ -0,0 +1,286 @@
+use super::{read_answers, Options};
+use anyhow::{ensure, Context, Result};
+use std::{fs, io::ErrorKind, path::Path};
+
+/// Returns every non-overlapping piece of `text` between `open` and `close`,
+/// along with the offset it starts at.
+fn between<'a>(text: &'a str, open: &str, close: &str) -> Vec<(usize, &'a str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find(open) {
+        let start = offset + start + open.len();
+        let Some(len) = text[start..].find(close) else {
+            break;
+        };
+        found.push((start, &text[start..start + len]));
+        offset = start + len + close.len();
+    }
+    found
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let entity = rest
+            .find(';')
+            .map(|end| (&rest[1..end], end))
+            .and_then(|(name, end)| {
+                let c = match name {
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "amp" => Some('&'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    _ => name
+                        .strip_prefix("#x")
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                        .or_else(|| name.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                        .and_then(char::from_u32),
+                };
+                c.map(|c| (c, end))
+            });
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn to_text(html: &str) -> String {
+    decode_entities(&strip_tags(html))
+}
+
+/// One part of the puzzle description: its first example block, if it has
+/// one, and the last emphasised code in it, which is normally the answer for
+/// that example.
+struct Part {
+    example: Option<String>,
+    answer: Option<String>,
+}
+
+fn parse_part(article: &str) -> Part {
+    let example = between(article, "<pre><code>", "</code></pre>")
+        .first()
+        .map(|(_, block)| {
+            let mut text = to_text(block);
+            if !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text
+        });
+    let answer = between(article, "<code><em>", "</em></code>")
+        .into_iter()
+        .chain(between(article, "<em><code>", "</code></em>"))
+        .max_by_key(|(offset, _)| *offset)
+        .map(|(_, answer)| to_text(answer))
+        .filter(|answer| !answer.is_empty() && !answer.contains(char::is_whitespace));
+    Part { example, answer }
+}
+
+/// Whether `path` holds something worth keeping. `new-day` leaves an empty
+/// example file, which is a placeholder to fill in.
+fn has_contents(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Sets the answers line for `name`, replacing an existing one or adding one
+/// at the end.
+fn set_answers(contents: &str, name: &str, line: &str) -> String {
+    let mut replaced = false;
+    let mut lines = contents
+        .lines()
+        .map(|existing| {
+            if existing.split_whitespace().next() == Some(name) {
+                replaced = true;
+                line
+            } else {
+                existing
+            }
+        })
+        .collect::<Vec<_>>();
+    if !replaced {
+        lines.push(line);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Reads a saved puzzle page and writes its examples as fixtures. Part two's
+/// answer is attached to part one's example unless part two has its own.
+pub fn extract_examples(day: u32, options: &Options) -> Result<()> {
+    ensure!(
+        options.files.len() == 1,
+        "extract-examples needs exactly one page"
+    );
+    let page_path = &options.files[0];
+    let page = fs::read_to_string(page_path)
+        .with_context(|| format!("Failed to read {}", page_path.display()))?;
+
+    let articles = between(&page, "<article", "</article>");
+    let parts = if articles.is_empty() {
+        vec![parse_part(&page)]
+    } else {
+        articles
+            .into_iter()
+            .map(|(_, article)| parse_part(article))
+            .collect()
+    };
+
+    // Each distinct example becomes a fixture, with a slot for each part's answer.
+    let mut fixtures: Vec<(String, [Option<String>; 2])> = Vec::new();
+    for (i, part) in parts.iter().take(2).enumerate() {
+        let example = match (&part.example, fixtures.last()) {
+            (Some(example), _) => example.clone(),
+            (None, Some((example, _))) => example.clone(),
+            (None, None) => continue,
+        };
+        let index = match fixtures.iter().position(|(e, _)| *e == example) {
+            Some(index) => index,
+            None => {
+                fixtures.push((example, [None, None]));
+                fixtures.len() - 1
+            }
+        };
+        fixtures[index].1[i] = part.answer.clone();
+    }
+    ensure!(
+        !fixtures.is_empty(),
+        "No examples found in {}",
+        page_path.display()
+    );
+
+    let answers_path = options.res_dir.join("answers.txt");
+    let answers = read_answers(&answers_path)?;
+    let mut contents = match fs::read_to_string(&answers_path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", answers_path.display()))
+        }
+    };
+    for (i, (example, [part_a, part_b])) in fixtures.into_iter().enumerate() {
+        let name = match i {
+            0 => format!("example{day:02}"),
+            _ => format!("example{day:02}-{}", i + 1),
+        };
+        let path = options.res_dir.join(format!("{name}.txt"));
+        if has_contents(&path) {
+            println!("Skipped {}, which already exists", path.display());
+            continue;
+        }
+        fs::write(&path, example).with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("Created {}", path.display());
+
+        // `new-day` adds a `? ?` line, which is only a placeholder.
+        if answers
+            .get(&name)
+            .is_some_and(|answers| answers.iter().any(Option::is_some))
+        {
+            println!(
+                "Skipped {name} in {}, which already has answers",
+                answers_path.display()
+            );
+            continue;
+        }
+        let part_a = part_a.as_deref().unwrap_or("?");
+        let part_b = part_b.as_deref().unwrap_or("?");
+        contents = set_answers(&contents, &name, &format!("{name} {part_a} {part_b}"));
+        println!(
+            "Added {name} {part_a} {part_b} to {}",
+            answers_path.display()
+        );
+    }
+    fs::write(&answers_path, contents)
+        .with_context(|| format!("Failed to write {}", answers_path.display()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_text_between_markers() {
+        let text = "a<b>one</b>c<b>two</b><b>three";
+        assert_eq!(between(text, "<b>", "</b>"), [(4, "one"), (15, "two")]);
+        assert!(between(text, "<i>", "</i>").is_empty());
+        // Pieces don't overlap, so the second open inside the first is text.
+        assert_eq!(between("[[x]]", "[", "]"), [(1, "[x")]);
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c &gt; d"),
+            "a < b && c > d"
+        );
+        assert_eq!(decode_entities("&quot;&apos;&#65;&#x42;"), "\"'AB");
+        // Anything unrecognised is left as it is.
+        assert_eq!(
+            decode_entities("AT&T &bogus; &#xzz; &"),
+            "AT&T &bogus; &#xzz; &"
+        );
+    }
+
+    #[test]
+    fn parses_parts() {
+        let article = "<p>For example:</p>\
+            <pre><code>1 &lt; 2\n3\n</code></pre>\
+            <pre><code>ignored</code></pre>\
+            <p>This gives <code><em>4</em></code>, then <em><code>11</code></em>.</p>";
+        let part = parse_part(article);
+        assert_eq!(part.example.as_deref(), Some("1 < 2\n3\n"));
+        assert_eq!(part.answer.as_deref(), Some("11"));
+
+        // A newline is added to the example, and an answer with spaces is
+        // more likely prose than an answer.
+        let part = parse_part("<pre><code><em>x</em>y</code></pre><code><em>a b</em></code>");
+        assert_eq!(part.example.as_deref(), Some("xy\n"));
+        assert_eq!(part.answer, None);
+
+        let part = parse_part("<p>No examples.</p>");
+        assert_eq!((part.example, part.answer), (None, None));
+    }
+
+    #[test]
+    fn sets_answers_lines() {
+        let contents = "input01 1 2\nexample02 ? ?\ninput02 ? ?\n";
+        assert_eq!(
+            set_answers(contents, "example02", "example02 5 ?"),
+            "input01 1 2\nexample02 5 ?\ninput02 ? ?\n"
+        );
+        assert_eq!(
+            set_answers(contents, "example02-2", "example02-2 7 8"),
+            "input01 1 2\nexample02 ? ?\ninput02 ? ?\nexample02-2 7 8\n"
+        );
+        assert_eq!(
+            set_answers("", "example01", "example01 1 ?"),
+            "example01 1 ?\n"
+        );
+    }
+}
@@ -0,0 +1,917 @@
+use crate::{
+    alloc::{self, AllocMeter, AllocStats},
+    budget::Budget,
+    render::Image,
+};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use itertools::Itertools;
+use rustc_hash::FxHashMap as HashMap;
+use std::{
+    fmt::Display,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+mod extract;
+mod new_day;
+mod repl;
+mod serve;
+mod validate;
+mod watch;
+
+pub use repl::Session;
+
+/// Loads a day's input into a REPL session.
+pub type LoadSession = fn(&str) -> Result<Box<dyn Session>>;
+
+/// Draws a picture of a day's input.
+pub type Render = fn(&str) -> Result<Image>;
+
+/// Checks a day's input, returning each assumption it breaks.
+pub type Validate = fn(&str) -> Result<Vec<Violation>>;
+
+/// Lists the states a day's simulation passes through, one line per step.
+pub type Trace = fn(&str, &Budget) -> Result<Vec<String>>;
+
+/// Solves a day reading its input as it goes, holding at most about the given
+/// number of bytes of it in memory.
+pub type Stream = fn(&mut dyn BufRead, usize, &Run) -> Result<()>;
+
+/// A registered solution. `solve` reports its answers through the `Run`, and
+/// the optional hooks are added with the `with_*` methods.
+pub struct Day {
+    pub day: u32,
+    pub solve: fn(&str, &Run) -> Result<()>,
+    pub repl: Option<LoadSession>,
+    pub render: Option<Render>,
+    pub validate: Option<Validate>,
+    pub stream: Option<Stream>,
+    pub trace: Option<Trace>,
+}
+
+impl Day {
+    pub const fn new(day: u32, solve: fn(&str, &Run) -> Result<()>) -> Self {
+        Self {
+            day,
+            solve,
+            repl: None,
+            render: None,
+            validate: None,
+            stream: None,
+            trace: None,
+        }
+    }
+
+    pub const fn with_repl(mut self, load: LoadSession) -> Self {
+        self.repl = Some(load);
+        self
+    }
+
+    pub const fn with_render(mut self, render: Render) -> Self {
+        self.render = Some(render);
+        self
+    }
+
+    pub const fn with_validate(mut self, validate: Validate) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
+    pub const fn with_stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    pub const fn with_trace(mut self, trace: Trace) -> Self {
+        self.trace = Some(trace);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    fn index(self) -> usize {
+        match self {
+            Part::A => 0,
+            Part::B => 1,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "A"),
+            Part::B => write!(f, "B"),
+        }
+    }
+}
+
+/// An assumption a day's input breaks. Most matter to both parts, but when
+/// only one part relies on it the other is still solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub part: Option<Part>,
+    pub message: String,
+}
+
+impl Violation {
+    /// An assumption both parts rely on.
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            part: None,
+            message: message.into(),
+        }
+    }
+
+    /// An assumption only `part` relies on.
+    pub fn of_part(part: Part, message: impl Into<String>) -> Self {
+        Self {
+            part: Some(part),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.part {
+            Some(part) => write!(f, "Part {part}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// One year's solutions. `root` is the consuming crate's directory, holding
+/// `res/` and `src/days/`.
+pub struct Year {
+    pub name: &'static str,
+    pub root: &'static str,
+    pub days: &'static [Day],
+}
+
+pub type Answers = [Option<String>; 2];
+
+/// Allocations for the whole day, and split at the moment each part's answer
+/// is reported. A lap only covers what was allocated since the previous
+/// answer, so work shared by both parts, or done for part B before part A's
+/// answer, counts towards the earlier lap. Days don't mark where a part's own
+/// work starts, so this is as close as the runner can get.
+#[derive(Debug, Clone, Default)]
+pub struct DayAllocs {
+    /// Each answer reported, in order, with what was allocated before it.
+    pub laps: Vec<(Part, AllocStats)>,
+    pub total: AllocStats,
+}
+
+impl DayAllocs {
+    /// Names each lap by the answers it falls between.
+    pub fn lap_names(&self) -> impl Iterator<Item = (String, AllocStats)> + '_ {
+        let previous = [None]
+            .into_iter()
+            .chain(self.laps.iter().map(|(part, _)| Some(*part)));
+        previous.zip(&self.laps).map(|(previous, (part, stats))| {
+            let name = match previous {
+                None => format!("before part {part}'s answer"),
+                Some(previous) => format!("between part {previous}'s and {part}'s answers"),
+            };
+            (name, *stats)
+        })
+    }
+}
+
+struct AllocTracker {
+    meter: AllocMeter,
+    allocs: DayAllocs,
+}
+
+impl AllocTracker {
+    fn lap(&mut self) -> AllocStats {
+        let stats = self.meter.lap();
+        let total = &mut self.allocs.total;
+        total.allocations += stats.allocations;
+        total.bytes += stats.bytes;
+        total.peak = total.peak.max(stats.peak);
+        stats
+    }
+}
+
+/// Everything a day's `solve` gets from the runner besides its input.
+pub struct Run {
+    budget: Budget,
+    skipped: [bool; 2],
+    answers: Mutex<Answers>,
+    explanation: Option<Mutex<Vec<String>>>,
+    stats: Option<Mutex<Vec<String>>>,
+    allocs: Option<Mutex<AllocTracker>>,
+}
+
+impl Run {
+    pub fn new(budget: Budget) -> Self {
+        Self {
+            budget,
+            skipped: [false; 2],
+            answers: Mutex::default(),
+            explanation: None,
+            stats: None,
+            allocs: None,
+        }
+    }
+
+    /// Counts allocations from now on, when the counting allocator is in use.
+    fn with_alloc_stats(mut self) -> Self {
+        self.allocs = Some(Mutex::new(AllocTracker {
+            meter: AllocMeter::start(),
+            allocs: DayAllocs::default(),
+        }));
+        self
+    }
+
+    /// Drops the answer to `part`, whose assumptions the input breaks.
+    fn with_skipped(mut self, part: Part) -> Self {
+        self.skipped[part.index()] = true;
+        self
+    }
+
+    /// Collects what the day passes to `explain`, which is otherwise dropped.
+    pub fn with_explanation(mut self) -> Self {
+        self.explanation = Some(Mutex::default());
+        self
+    }
+
+    pub fn budget(&self) -> &Budget {
+        &self.budget
+    }
+
+    /// Whether `part`'s answer is wanted. It isn't when the input breaks an
+    /// assumption of that part, and days should check this before solving a
+    /// part that might fail or run away on such an input.
+    pub fn wants(&self, part: Part) -> bool {
+        !self.skipped[part.index()]
+    }
+
+    /// Collects what the day passes to `stat`, which is otherwise dropped.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = Some(Mutex::default());
+        self
+    }
+
+    /// Whether the explanation is wanted. Days should check this before doing
+    /// any extra work just to explain themselves.
+    pub fn explaining(&self) -> bool {
+        self.explanation.is_some()
+    }
+
+    /// Adds a line to the narrative of how the answers were reached.
+    pub fn explain(&self, line: impl Display) {
+        if let Some(explanation) = &self.explanation {
+            explanation.lock().unwrap().push(line.to_string());
+        }
+    }
+
+    /// Records how the solver performed, such as a cache's hit rate. Kept
+    /// apart from the explanation, which is about the puzzle.
+    pub fn stat(&self, line: impl Display) {
+        if let Some(stats) = &self.stats {
+            stats.lock().unwrap().push(line.to_string());
+        }
+    }
+
+    pub fn part_a(&self, answer: impl Display) {
+        self.answer(Part::A, answer);
+    }
+
+    pub fn part_b(&self, answer: impl Display) {
+        self.answer(Part::B, answer);
+    }
+
+    /// The answer reported for `part` so far, for tests to check.
+    pub fn answer_for(&self, part: Part) -> Option<String> {
+        self.answers.lock().unwrap()[part.index()].clone()
+    }
+
+    fn answer(&self, part: Part, answer: impl Display) {
+        if !self.wants(part) {
+            return;
+        }
+        self.answers.lock().unwrap()[part.index()] = Some(answer.to_string());
+        if let Some(allocs) = &self.allocs {
+            let mut allocs = allocs.lock().unwrap();
+            let stats = allocs.lap();
+            allocs.allocs.laps.push((part, stats));
+        }
+    }
+
+    fn finish(self, elapsed: Duration) -> Solved {
+        let allocs = self.allocs.map(|allocs| {
+            let mut allocs = allocs.into_inner().unwrap();
+            allocs.lap();
+            allocs.allocs
+        });
+        Solved {
+            skipped: Vec::new(),
+            answers: self.answers.into_inner().unwrap(),
+            explanation: self
+                .explanation
+                .map(|explanation| explanation.into_inner().unwrap())
+                .unwrap_or_default(),
+            stats: self
+                .stats
+                .map(|stats| stats.into_inner().unwrap())
+                .unwrap_or_default(),
+            elapsed,
+            allocs,
+        }
+    }
+}
+
+/// What solving one input produced.
+#[derive(Debug)]
+struct Solved {
+    /// The broken assumptions that kept parts from being solved.
+    skipped: Vec<Violation>,
+    answers: Answers,
+    explanation: Vec<String>,
+    stats: Vec<String>,
+    elapsed: Duration,
+    allocs: Option<DayAllocs>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Examples,
+    Watch,
+    NewDay,
+    ExtractExamples,
+    Repl,
+    Serve,
+    Validate,
+}
+
+impl Command {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "run" => Some(Command::Run),
+            "examples" => Some(Command::Examples),
+            "watch" => Some(Command::Watch),
+            "new-day" => Some(Command::NewDay),
+            "extract-examples" => Some(Command::ExtractExamples),
+            "repl" => Some(Command::Repl),
+            "serve" => Some(Command::Serve),
+            "validate" => Some(Command::Validate),
+            _ => None,
+        }
+    }
+
+    fn single_day(self) -> bool {
+        matches!(
+            self,
+            Command::Watch | Command::NewDay | Command::ExtractExamples | Command::Repl
+        )
+    }
+
+    /// Whether arguments after the day are file paths.
+    fn takes_files(self) -> bool {
+        matches!(
+            self,
+            Command::Watch | Command::ExtractExamples | Command::Repl
+        )
+    }
+}
+
+pub struct Options {
+    pub command: Command,
+    pub days: Vec<u32>,
+    pub files: Vec<PathBuf>,
+    pub interval: Duration,
+    pub threads: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub max_steps: Option<u64>,
+    pub max_items: Option<usize>,
+    pub res_dir: PathBuf,
+    pub port: u16,
+    pub explain: bool,
+    pub stats: bool,
+    pub memory: Option<usize>,
+}
+
+fn usage(name: &str) -> String {
+    format!(
+        "\
+Usage: {name} [DAY...] [OPTIONS]
+       {name} examples [DAY...] [OPTIONS]
+       {name} watch DAY [FILE...] [OPTIONS]
+       {name} new-day DAY
+       {name} extract-examples DAY PAGE
+       {name} repl DAY [FILE] [OPTIONS]
+       {name} serve [DAY...] [OPTIONS]
+       {name} validate [DAY...] [OPTIONS]
+
+Runs the given days, or every day if none are given. `examples` runs the days
+against their example inputs instead. `watch` re-solves a day whenever one of
+the files changes, defaulting to the day's input. `new-day` creates the files
+for a new day from the template. `extract-examples` saves the examples and
+their answers from a downloaded puzzle page. `repl` loads a day's input, by
+default its puzzle input, for inspecting interactively. `serve` solves the days
+and shows the results on a web page at http://localhost:PORT/. `validate`
+checks the days' inputs against the assumptions their solvers make, which is
+also done before solving or loading a puzzle input. A part whose assumptions
+are broken isn't solved.
+
+Options:
+  --threads N      Size of the rayon thread pool
+  --timeout MS     Per-day wall-clock limit in milliseconds
+  --max-steps N    Per-day step limit
+  --max-items N    Per-day limit on any working collection's size
+  --res DIR        Directory containing inputs and answers.txt
+  --explain        Print how each answer was reached, for days that can
+  --stats          Print how the solvers performed, such as cache hit rates
+  --memory BYTES   Stream the input through about this much memory rather
+                   than loading it, for days that can
+  --interval MS    How often `watch` polls the files (default 500)
+  --port N         Port for `serve` to listen on (default 8024)
+
+The time, step and item limits are checked by the days as they work, so a day
+stops at its next check after a limit runs out rather than at once."
+    )
+}
+
+impl Options {
+    pub fn parse(year: &Year, args: impl Iterator<Item = String>) -> Result<Self> {
+        fn value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T>
+        where
+            T::Err: std::error::Error + Send + Sync + 'static,
+        {
+            let value = value.with_context(|| format!("{flag} needs a value"))?;
+            value
+                .parse()
+                .with_context(|| format!("Invalid value for {flag}: {value}"))
+        }
+
+        let mut options = Options {
+            command: Command::Run,
+            days: Vec::new(),
+            files: Vec::new(),
+            interval: Duration::from_millis(500),
+            threads: None,
+            timeout: None,
+            max_steps: None,
+            max_items: None,
+            res_dir: Path::new(year.root).join("res"),
+            port: 8024,
+            explain: false,
+            stats: false,
+            memory: None,
+        };
+        let usage = usage(year.name);
+        let mut args = args.peekable();
+        if let Some(command) = args.peek().and_then(|arg| Command::from_name(arg)) {
+            options.command = command;
+            args.next();
+        }
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--threads" => options.threads = Some(value(&arg, args.next())?),
+                "--timeout" => {
+                    options.timeout = Some(Duration::from_millis(value(&arg, args.next())?))
+                }
+                "--max-steps" => options.max_steps = Some(value(&arg, args.next())?),
+                "--max-items" => options.max_items = Some(value(&arg, args.next())?),
+                "--res" => options.res_dir = value(&arg, args.next())?,
+                "--explain" => options.explain = true,
+                "--stats" => options.stats = true,
+                "--memory" => options.memory = Some(value(&arg, args.next())?),
+                "--port" => options.port = value(&arg, args.next())?,
+                "--interval" => options.interval = Duration::from_millis(value(&arg, args.next())?),
+                "-h" | "--help" => bail!("{usage}"),
+                _ if options.command.takes_files() && !options.days.is_empty() => {
+                    options.files.push(arg.into())
+                }
+                _ => options.days.push(
+                    arg.parse()
+                        .with_context(|| format!("Unexpected argument: {arg}\n\n{usage}"))?,
+                ),
+            }
+        }
+        if options.command.single_day() {
+            ensure!(
+                options.days.len() == 1,
+                "Expected exactly one day\n\n{usage}"
+            );
+        }
+        Ok(options)
+    }
+
+    /// The budget for one day. Limits not given on the command line fall back
+    /// to the `AOC_*` environment variables.
+    fn budget(&self) -> Budget {
+        let mut budget = Budget::from_env();
+        if let Some(max_steps) = self.max_steps {
+            budget = budget.with_max_steps(max_steps);
+        }
+        if let Some(max_items) = self.max_items {
+            budget = budget.with_max_items(max_items);
+        }
+        if let Some(timeout) = self.timeout {
+            budget = budget.with_timeout(timeout);
+        }
+        budget
+    }
+
+    fn input_path(&self, day: u32) -> PathBuf {
+        self.res_dir.join(format!("input{day:02}.txt"))
+    }
+
+    /// Example inputs are named `exampleNN.txt`, with any further examples
+    /// for the same day as `exampleNN-2.txt` and so on.
+    fn example_paths(&self, day: u32) -> Result<Vec<PathBuf>> {
+        let name = format!("example{day:02}");
+        let is_example = |path: &Path| {
+            let stem = path.file_stem().and_then(|stem| stem.to_str());
+            path.extension().is_some_and(|ext| ext == "txt")
+                && stem.is_some_and(|stem| {
+                    stem == name
+                        || stem
+                            .strip_prefix(&name)
+                            .is_some_and(|rest| rest.starts_with('-'))
+                })
+        };
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(&self.res_dir)
+            .with_context(|| format!("Failed to read {}", self.res_dir.display()))?
+        {
+            let path = entry?.path();
+            if is_example(&path) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn configure_threads(threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .context("Failed to configure thread pool")
+}
+
+#[cfg(not(feature = "parallel"))]
+fn configure_threads(threads: usize) -> Result<()> {
+    ensure!(
+        threads <= 1,
+        "--threads {threads} needs the parallel feature, which this build lacks"
+    );
+    Ok(())
+}
+
+/// Expected answers, one line per input file: the file's name without its
+/// extension, followed by the part A and part B answers. `?` marks an answer
+/// that isn't known yet.
+fn read_answers(path: &Path) -> Result<HashMap<String, Answers>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::default()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            ensure!(fields.len() == 3, "Invalid answers line: {line}");
+            let answer = |field: &str| (field != "?").then(|| field.to_owned());
+            Ok((fields[0].to_owned(), [answer(fields[1]), answer(fields[2])]))
+        })
+        .collect()
+}
+
+/// Runs `f`, turning a panic into an error that names `what` panicked.
+fn catch_panic<T>(what: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned());
+        anyhow!("{what} panicked: {message}")
+    })?
+}
+
+/// Solves one input, turning a panic in the solver into an error so that a
+/// malformed input can't take the whole runner down.
+fn solve(
+    options: &Options,
+    skipped: &[Violation],
+    solver: impl FnOnce(&Run) -> Result<()>,
+) -> Result<Solved> {
+    let mut run = Run::new(options.budget());
+    for part in skipped.iter().filter_map(|violation| violation.part) {
+        run = run.with_skipped(part);
+    }
+    if options.explain {
+        run = run.with_explanation();
+    }
+    if options.stats {
+        run = run.with_stats();
+    }
+    if alloc::ENABLED {
+        run = run.with_alloc_stats();
+    }
+    let start = Instant::now();
+    catch_panic("Solver", || solver(&run))?;
+    let mut solved = run.finish(start.elapsed());
+    solved.skipped = skipped.to_vec();
+    Ok(solved)
+}
+
+/// Solves an input, first checking a puzzle input with the day's validator.
+/// Examples are often smaller than the puzzle inputs the validators expect,
+/// so they aren't checked. An input that breaks an assumption of both parts
+/// isn't solved, and one that breaks only one part's has the other solved.
+fn solve_input(day: &Day, input: &str, puzzle_input: bool, options: &Options) -> Result<Solved> {
+    let violations = validate_input(day, input, puzzle_input)?;
+    solve(options, &violations, |run| (day.solve)(input, run))
+}
+
+/// Checks a puzzle input with the day's validator, failing if it breaks an
+/// assumption of both parts. Returns the assumptions only one part relies on.
+fn validate_input(day: &Day, input: &str, puzzle_input: bool) -> Result<Vec<Violation>> {
+    let violations = match day.validate.filter(|_| puzzle_input) {
+        Some(validate) => validate(input)?,
+        None => Vec::new(),
+    };
+    let both = violations
+        .iter()
+        .filter(|violation| violation.part.is_none())
+        .collect::<Vec<_>>();
+    if !both.is_empty() {
+        let plural = if both.len() == 1 { "" } else { "s" };
+        bail!(
+            "Input breaks {} assumption{plural}, so it wasn't solved: {}",
+            both.len(),
+            both.iter().map(|violation| &violation.message).join("; ")
+        );
+    }
+    Ok(violations)
+}
+
+fn run_file(
+    day: &Day,
+    path: &Path,
+    options: &Options,
+    answers: &HashMap<String, Answers>,
+    allocs: &mut Vec<(String, DayAllocs)>,
+) -> Result<()> {
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let label = if *path == options.input_path(day.day) {
+        format!("Day {:02}", day.day)
+    } else {
+        format!("Day {:02} ({name})", day.day)
+    };
+    let expected = answers.get(name);
+    // Validators need the whole input, so streamed inputs aren't checked.
+    if let Some((stream, memory)) = day.stream.zip(options.memory) {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let solved = solve(options, &[], |run| {
+            stream(&mut BufReader::new(file), memory, run)
+        })?;
+        return report(&label, expected, solved, allocs);
+    }
+
+    let input =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let puzzle_input = *path == options.input_path(day.day);
+    let solved = solve_input(day, &input, puzzle_input, options)?;
+    report(&label, expected, solved, allocs)
+}
+
+/// Prints what solving an input produced, failing if an answer is wrong or a
+/// part was skipped.
+fn report(
+    label: &str,
+    expected: Option<&Answers>,
+    solved: Solved,
+    allocs: &mut Vec<(String, DayAllocs)>,
+) -> Result<()> {
+    let mut mismatches = 0;
+    for (part, answer) in [Part::A, Part::B].into_iter().zip(solved.answers) {
+        let Some(answer) = answer else {
+            continue;
+        };
+        match expected.and_then(|expected| expected[part.index()].as_ref()) {
+            Some(expected) if *expected != answer => {
+                println!("{label}, part {part}: {answer} (expected {expected})");
+                mismatches += 1;
+            }
+            _ => println!("{label}, part {part}: {answer}"),
+        }
+    }
+    let skipped = [Part::A, Part::B]
+        .into_iter()
+        .filter(|&part| solved.skipped.iter().any(|v| v.part == Some(part)))
+        .collect::<Vec<_>>();
+    for violation in &solved.skipped {
+        println!("{label}: {violation}");
+    }
+    for line in &solved.explanation {
+        println!("  {line}");
+    }
+    println!("{label} took {:.2?}", solved.elapsed);
+    for line in &solved.stats {
+        println!("{label}: {line}");
+    }
+    if let Some(day_allocs) = solved.allocs {
+        for (name, stats) in day_allocs.lap_names() {
+            println!("{label}, {name}: {stats}");
+        }
+        allocs.push((label.to_owned(), day_allocs));
+    }
+    ensure!(mismatches == 0, "{mismatches} wrong answers");
+    ensure!(
+        skipped.is_empty(),
+        "Input breaks assumptions of part {}, so it wasn't solved",
+        skipped.iter().join(" and ")
+    );
+    Ok(())
+}
+
+/// Lists every day run and its laps, the heaviest allocators first.
+fn print_alloc_summary(allocs: &mut [(String, DayAllocs)]) {
+    allocs.sort_by_key(|(_, day_allocs)| std::cmp::Reverse(day_allocs.total.bytes));
+    println!();
+    println!(
+        "{:<34} {:>12} {:>12} {:>12}",
+        "Allocations", "count", "bytes", "peak"
+    );
+    for (label, day_allocs) in allocs.iter() {
+        let laps = day_allocs
+            .lap_names()
+            .map(|(name, stats)| (format!("  {name}"), stats));
+        for (name, stats) in [(label.clone(), day_allocs.total)].into_iter().chain(laps) {
+            println!(
+                "{name:<34} {:>12} {:>12} {:>12}",
+                stats.allocations,
+                alloc::format_bytes(stats.bytes),
+                alloc::format_bytes(stats.peak)
+            );
+        }
+    }
+}
+
+pub fn main(year: &Year) -> Result<()> {
+    let options = Options::parse(year, std::env::args().skip(1))?;
+    if let Some(threads) = options.threads {
+        configure_threads(threads)?;
+    }
+    if options.command == Command::NewDay {
+        return new_day::new_day(options.days[0], year, &options);
+    }
+    if options.command == Command::ExtractExamples {
+        return extract::extract_examples(options.days[0], &options);
+    }
+    let answers = read_answers(&options.res_dir.join("answers.txt"))?;
+
+    let selected = year
+        .days
+        .iter()
+        .filter(|day| options.days.is_empty() || options.days.contains(&day.day))
+        .collect::<Vec<_>>();
+    for day in &options.days {
+        ensure!(selected.iter().any(|d| d.day == *day), "No such day: {day}");
+    }
+    match options.command {
+        Command::Watch => return watch::watch(selected[0], &options),
+        Command::Repl => return repl::repl(selected[0], &options),
+        Command::Serve => return serve::serve(year.name, &selected, &options),
+        Command::Validate => return validate::validate(&selected, &options),
+        _ => {}
+    }
+
+    let mut failed = Vec::new();
+    let mut allocs = Vec::new();
+    for day in selected {
+        let paths = match options.command {
+            Command::Examples => options.example_paths(day.day)?,
+            _ => vec![options.input_path(day.day)],
+        };
+        for path in paths {
+            // Days without a puzzle input yet don't fail a run of every day.
+            if options.days.is_empty() && !path.exists() {
+                println!("{} missing, skipped", path.display());
+                continue;
+            }
+            if let Err(e) = run_file(day, &path, &options, &answers, &mut allocs) {
+                println!("{} failed: {e:#}", path.display());
+                failed.push(day.day);
+            }
+        }
+    }
+    if !allocs.is_empty() {
+        print_alloc_summary(&mut allocs);
+    }
+    failed.dedup();
+    ensure!(failed.is_empty(), "Failed days: {failed:?}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve_both(_: &str, run: &Run) -> Result<()> {
+        run.part_a(1);
+        run.part_b(2);
+        Ok(())
+    }
+
+    /// Flags part B when the input mentions it, and both parts when it's empty.
+    fn validate(input: &str) -> Result<Vec<Violation>> {
+        let mut violations = Vec::new();
+        if input.contains('b') {
+            violations.push(Violation::of_part(Part::B, "no b allowed"));
+        }
+        if input.is_empty() {
+            violations.push(Violation::new("empty"));
+        }
+        Ok(violations)
+    }
+
+    pub(super) fn options() -> Options {
+        let year = Year {
+            name: "test",
+            root: ".",
+            days: &[],
+        };
+        Options::parse(&year, std::iter::empty()).unwrap()
+    }
+
+    #[test]
+    fn skips_parts_whose_assumptions_break() {
+        let day = Day::new(1, solve_both).with_validate(validate);
+        let options = options();
+
+        let solved = solve_input(&day, "a", true, &options).unwrap();
+        assert_eq!(solved.answers, [Some("1".to_owned()), Some("2".to_owned())]);
+        assert!(solved.skipped.is_empty());
+
+        let solved = solve_input(&day, "ab", true, &options).unwrap();
+        assert_eq!(solved.answers, [Some("1".to_owned()), None]);
+        assert_eq!(
+            solved.skipped,
+            [Violation::of_part(Part::B, "no b allowed")]
+        );
+
+        let error = solve_input(&day, "", true, &options).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Input breaks 1 assumption, so it wasn't solved: empty"
+        );
+
+        // Examples aren't validated.
+        let solved = solve_input(&day, "", false, &options).unwrap();
+        assert_eq!(solved.answers, [Some("1".to_owned()), Some("2".to_owned())]);
+    }
+
+    #[test]
+    fn names_laps_by_the_answers_around_them() {
+        let stats = |allocations| AllocStats {
+            allocations,
+            ..AllocStats::default()
+        };
+        let allocs = DayAllocs {
+            laps: vec![(Part::B, stats(1)), (Part::A, stats(2))],
+            total: stats(3),
+        };
+        assert_eq!(
+            allocs.lap_names().collect::<Vec<_>>(),
+            [
+                ("before part B's answer".to_owned(), stats(1)),
+                ("between part B's and A's answers".to_owned(), stats(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn catches_panics() {
+        let day = Day::new(1, |_, _| panic!("oops"));
+        let error = solve_input(&day, "", false, &options()).unwrap_err();
+        assert_eq!(error.to_string(), "Solver panicked: oops");
+    }
+}
@@ -0,0 +1,221 @@
+use super::{read_answers, Options, Year};
+use anyhow::{ensure, Context, Result};
+use itertools::Itertools;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+/// The starting point for every day's module, with `DD` standing for the
+/// two-digit day.
+const TEMPLATE: &str = "\
+use aoc::runner::Run;
+use anyhow::Result;
+
+fn parse_input(input: &str) -> Result<Vec<&str>> {
+    Ok(input.lines().collect())
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let lines = parse_input(input)?;
+
+    let part_a = lines.len();
+    run.part_a(part_a);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::{budget::Budget, runner::Part};
+
+    #[test]
+    fn example() {
+        let run = Run::new(Budget::default());
+        solve(include_str!(\"../../res/exampleDD.txt\"), &run).unwrap();
+        // Replace with the example's answers once they're known.
+        assert!(run.answer_for(Part::A).is_some());
+    }
+}
+";
+
+/// Adds the day's module and registry entry to `days/mod.rs`, each in day
+/// order. Existing entries may carry hooks, so the rest of the file is left as
+/// it is.
+fn register_day(days_mod: &str, day: u32) -> Result<String> {
+    fn day_of(line: &str, prefix: &str) -> Option<u32> {
+        let rest = line.trim_start().strip_prefix(prefix)?;
+        rest[..rest.find(|c: char| !c.is_ascii_digit())?]
+            .parse()
+            .ok()
+    }
+
+    // An empty registry may be on one line.
+    let days_mod = days_mod.replace("= &[];", "= &[\n];");
+    let mut lines = days_mod.lines().collect_vec();
+    let entries_end = lines
+        .iter()
+        .position(|line| *line == "];")
+        .context("No DAYS registry found")?;
+    let entry = format!("    Day::new({day}, day{day:02}::solve),");
+    let entry_at = lines
+        .iter()
+        .position(|line| day_of(line, "Day::new(").is_some_and(|d| d > day))
+        .unwrap_or(entries_end);
+    lines.insert(entry_at, &entry);
+
+    let module = format!("pub mod day{day:02};");
+    let modules = lines
+        .iter()
+        .positions(|line| day_of(line, "pub mod day").is_some())
+        .collect_vec();
+    let module_at = modules
+        .iter()
+        .copied()
+        .find(|&i| day_of(lines[i], "pub mod day").is_some_and(|d| d > day))
+        .or_else(|| modules.last().map(|i| i + 1));
+    match module_at {
+        Some(module_at) => lines.insert(module_at, &module),
+        // The first day's module goes just before the registry.
+        None => {
+            let registry = lines
+                .iter()
+                .position(|line| line.starts_with("pub const DAYS"))
+                .context("No DAYS registry found")?;
+            lines.splice(registry..registry, [module.as_str(), ""]);
+        }
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn create(path: &Path, contents: &str) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    file.write_all(contents.as_bytes())?;
+    println!("Created {}", path.display());
+    Ok(())
+}
+
+pub fn new_day(day: u32, year: &Year, options: &Options) -> Result<()> {
+    let days = year.days;
+    ensure!((1..=25).contains(&day), "Day must be between 1 and 25");
+    ensure!(
+        days.iter().all(|d| d.day != day),
+        "Day {day} is already registered"
+    );
+
+    let src_dir = Path::new(year.root).join("src/days");
+    create(
+        &src_dir.join(format!("day{day:02}.rs")),
+        &TEMPLATE.replace("DD", &format!("{day:02}")),
+    )?;
+
+    let days_mod = src_dir.join("mod.rs");
+    let contents = fs::read_to_string(&days_mod)
+        .with_context(|| format!("Failed to read {}", days_mod.display()))?;
+    fs::write(&days_mod, register_day(&contents, day)?)
+        .with_context(|| format!("Failed to write {}", days_mod.display()))?;
+    println!("Registered day {day} in {}", days_mod.display());
+
+    let input = options.input_path(day);
+    if !input.exists() {
+        create(&input, "")?;
+    }
+    let example = options.res_dir.join(format!("example{day:02}.txt"));
+    if !example.exists() {
+        create(&example, "")?;
+    }
+
+    let answers_path = options.res_dir.join("answers.txt");
+    let answers = read_answers(&answers_path)?;
+    let mut answers_file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&answers_path)
+        .with_context(|| format!("Failed to open {}", answers_path.display()))?;
+    for name in [format!("example{day:02}"), format!("input{day:02}")] {
+        if !answers.contains_key(&name) {
+            writeln!(answers_file, "{name} ? ?")?;
+            println!("Added {name} to {}", answers_path.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAYS_MOD: &str = "\
+use aoc::runner::Day;
+
+pub mod day01;
+pub mod day03;
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, day01::solve).with_repl(day01::repl),
+    Day::new(3, day03::solve),
+];
+";
+
+    #[test]
+    fn registers_days_in_order() {
+        assert_eq!(
+            register_day(DAYS_MOD, 2).unwrap(),
+            "\
+use aoc::runner::Day;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, day01::solve).with_repl(day01::repl),
+    Day::new(2, day02::solve),
+    Day::new(3, day03::solve),
+];
+"
+        );
+        let last = register_day(DAYS_MOD, 25).unwrap();
+        assert!(last.contains("pub mod day03;\npub mod day25;\n"));
+        assert!(last.contains("    Day::new(25, day25::solve),\n];"));
+    }
+
+    #[test]
+    fn registers_first_day() {
+        let expected = "\
+use aoc::runner::Day;
+
+pub mod day01;
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, day01::solve),
+];
+";
+        for empty in [
+            "use aoc::runner::Day;\n\npub const DAYS: &[Day] = &[\n];\n",
+            "use aoc::runner::Day;\n\npub const DAYS: &[Day] = &[];\n",
+        ] {
+            assert_eq!(register_day(empty, 1).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn needs_a_registry() {
+        assert!(register_day("pub mod day01;\n", 2).is_err());
+    }
+
+    #[test]
+    fn template_names_its_example() {
+        let template = TEMPLATE.replace("DD", "07");
+        assert!(template.contains("include_str!(\"../../res/example07.txt\")"));
+        assert!(!template.contains("DD"));
+    }
+}
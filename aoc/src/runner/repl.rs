@@ -0,0 +1,189 @@
+use super::{catch_panic, validate_input, Day, Options, Part, Violation};
+use crate::budget::Budget;
+use anyhow::{anyhow, bail, Context, Result};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// A day's parsed input, loaded into the REPL to be inspected and stepped
+/// through. Only `show` is required; the other commands report that the day
+/// doesn't support them.
+pub trait Session {
+    /// The current state, as text.
+    fn show(&self) -> String;
+
+    /// Advances the state by `n` steps.
+    fn step(&mut self, _n: usize) -> Result<()> {
+        bail!("This day can't be stepped through")
+    }
+
+    /// Solves `part` from the current state.
+    fn part(&self, _part: Part, _budget: &Budget) -> Result<String> {
+        bail!("This day can't solve from the REPL")
+    }
+
+    fn set(&mut self, param: &str, _value: &str) -> Result<()> {
+        bail!("No such parameter: {param}")
+    }
+
+    /// Saves an image of the current state.
+    fn render(&self, _path: &Path) -> Result<()> {
+        bail!("This day has nothing to render")
+    }
+}
+
+const HELP: &str = "\
+Commands:
+  show               Print the current state
+  step [N]           Advance N steps (default 1)
+  part a|b           Solve a part from the current state
+  set PARAM VALUE    Change a parameter of the state
+  render FILE        Save an image of the current state
+  help               Print this message
+  quit               Leave the REPL";
+
+fn run_command(
+    session: &mut dyn Session,
+    words: &[&str],
+    skipped: &[Violation],
+    options: &Options,
+) -> Result<()> {
+    match words {
+        ["show"] => print!("{}", session.show()),
+        ["step"] => session.step(1)?,
+        ["step", n] => session.step(n.parse().with_context(|| format!("Invalid count: {n}"))?)?,
+        ["part", part] => {
+            let part = match *part {
+                "a" | "A" => Part::A,
+                "b" | "B" => Part::B,
+                _ => bail!("No such part: {part}"),
+            };
+            if let Some(violation) = skipped.iter().find(|v| v.part == Some(part)) {
+                bail!(
+                    "Input breaks an assumption of part {part}: {}",
+                    violation.message
+                );
+            }
+            let answer = session.part(part, &options.budget())?;
+            println!("Part {part}: {answer}");
+        }
+        ["set", param, value] => session.set(param, value)?,
+        ["render", path] => {
+            session.render(Path::new(path))?;
+            println!("Saved {path}");
+        }
+        ["help"] => println!("{HELP}"),
+        _ => return Err(anyhow!("Unknown command: {}\n\n{HELP}", words.join(" "))),
+    }
+    Ok(())
+}
+
+/// Loads the day's input, defaulting to its puzzle input, and runs commands
+/// from stdin against it until `quit` or end of input. A puzzle input is
+/// validated first, and parts whose assumptions it breaks can't be solved. A
+/// failed or panicking command is reported rather than ending the REPL, though
+/// a panic part-way through `step` can leave the state part-way too.
+pub fn repl(day: &Day, options: &Options) -> Result<()> {
+    let load = day
+        .repl
+        .with_context(|| format!("Day {} has no REPL", day.day))?;
+    let path = options
+        .files
+        .first()
+        .cloned()
+        .unwrap_or_else(|| options.input_path(day.day));
+    let input =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let puzzle_input = path == options.input_path(day.day);
+    let skipped = validate_input(day, &input, puzzle_input)?;
+    let mut session = catch_panic("Loading", || load(&input))?;
+    println!("Loaded {}. Type `help` for commands.", path.display());
+    for violation in &skipped {
+        println!("{violation}");
+    }
+
+    let mut line = String::new();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        line.clear();
+        if io::stdin().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            [] => {}
+            ["quit"] | ["exit"] => return Ok(()),
+            _ => {
+                let result = catch_panic("Command", || {
+                    run_command(session.as_mut(), &words, &skipped, options)
+                });
+                if let Err(e) = result {
+                    println!("error: {e:#}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::tests::options;
+
+    struct Counter(usize);
+
+    impl Session for Counter {
+        fn show(&self) -> String {
+            format!("{}\n", self.0)
+        }
+
+        fn step(&mut self, n: usize) -> Result<()> {
+            assert!(self.0 + n <= 3, "counted past 3");
+            self.0 += n;
+            Ok(())
+        }
+
+        fn part(&self, _part: Part, _budget: &Budget) -> Result<String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    #[test]
+    fn refuses_skipped_parts() {
+        let mut session = Counter(0);
+        let skipped = [Violation::of_part(Part::B, "too small")];
+        run_command(&mut session, &["part", "a"], &skipped, &options()).unwrap();
+        let error = run_command(&mut session, &["part", "b"], &skipped, &options()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Input breaks an assumption of part B: too small"
+        );
+    }
+
+    #[test]
+    fn reports_panicking_commands() {
+        let mut session = Counter(0);
+        let mut run = |words: &[&str]| {
+            catch_panic("Command", || {
+                run_command(&mut session, words, &[], &options())
+            })
+        };
+        run(&["step", "2"]).unwrap();
+        let error = run(&["step", "2"]).unwrap_err();
+        assert_eq!(error.to_string(), "Command panicked: counted past 3");
+        run(&["step"]).unwrap();
+        assert_eq!(session.show(), "3\n");
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        let error = run_command(&mut Counter(0), &["jump"], &[], &options()).unwrap_err();
+        assert!(error.to_string().starts_with("Unknown command: jump"));
+        let error = run_command(&mut Counter(0), &["part", "c"], &[], &options()).unwrap_err();
+        assert_eq!(error.to_string(), "No such part: c");
+    }
+}
@@ -0,0 +1,429 @@
+use super::{read_answers, solve_input, Answers, Day, Options, Part};
+use crate::budget::Budget;
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::PathBuf,
+    time::Duration,
+};
+
+/// How long to wait for a client to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The most steps a trace records, which keeps a runaway simulation from
+/// tying up the server.
+const MAX_TRACE_STEPS: u64 = 1_000_000;
+
+/// The outcome of solving one day's input.
+struct Record {
+    day: u32,
+    path: PathBuf,
+    answers: Answers,
+    expected: Answers,
+    explanation: Vec<String>,
+    elapsed: Option<Duration>,
+    error: Option<String>,
+}
+
+impl Record {
+    fn status(&self, part: Part) -> &'static str {
+        let answer = self.answers[part.index()].as_ref();
+        match (answer, self.expected[part.index()].as_ref()) {
+            (None, _) => "missing",
+            (Some(_), None) => "unverified",
+            (Some(answer), Some(expected)) if answer == expected => "correct",
+            (Some(_), Some(_)) => "wrong",
+        }
+    }
+
+    fn to_json(&self) -> String {
+        fn opt(value: Option<&String>) -> String {
+            value.map_or_else(|| "null".to_owned(), |value| json_string(value))
+        }
+
+        let parts = [Part::A, Part::B]
+            .map(|part| {
+                format!(
+                    "{{\"part\": \"{part}\", \"answer\": {}, \"expected\": {}, \"status\": \"{}\"}}",
+                    opt(self.answers[part.index()].as_ref()),
+                    opt(self.expected[part.index()].as_ref()),
+                    self.status(part)
+                )
+            })
+            .join(", ");
+        let elapsed_ms = self.elapsed.map_or_else(
+            || "null".to_owned(),
+            |elapsed| format!("{:.3}", elapsed.as_secs_f64() * 1000.0),
+        );
+        format!(
+            "{{\"day\": {}, \"input\": {}, \"parts\": [{parts}], \"explanation\": [{}], \"elapsed_ms\": {elapsed_ms}, \"error\": {}}}",
+            self.day,
+            json_string(&self.path.display().to_string()),
+            self.explanation.iter().map(|line| json_string(line)).join(", "),
+            opt(self.error.as_ref())
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn solve_days(days: &[&Day], options: &Options) -> Result<Vec<Record>> {
+    let expected = read_answers(&options.res_dir.join("answers.txt"))?;
+    Ok(days
+        .iter()
+        .map(|day| {
+            let path = options.input_path(day.day);
+            let name = format!("input{:02}", day.day);
+            let result = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))
+                .and_then(|input| solve_input(day, &input, true, options));
+            let (answers, explanation, elapsed, error) = match result {
+                Ok(solved) => (
+                    solved.answers,
+                    solved.explanation,
+                    Some(solved.elapsed),
+                    (!solved.skipped.is_empty()).then(|| solved.skipped.iter().join("; ")),
+                ),
+                Err(e) => (Answers::default(), Vec::new(), None, Some(format!("{e:#}"))),
+            };
+            println!(
+                "Day {:02}: {}",
+                day.day,
+                error.as_deref().unwrap_or("solved")
+            );
+            Record {
+                day: day.day,
+                path,
+                answers,
+                expected: expected.get(&name).cloned().unwrap_or_default(),
+                explanation,
+                elapsed,
+                error,
+            }
+        })
+        .collect())
+}
+
+fn index_page(name: &str, days: &[&Day], records: &[Record]) -> String {
+    let mut rows = String::new();
+    for (day, record) in days.iter().zip(records) {
+        write!(rows, "<tr><td>{:02}</td>", record.day).unwrap();
+        for part in [Part::A, Part::B] {
+            let answer = record.answers[part.index()].as_deref().unwrap_or("-");
+            let status = record.status(part);
+            write!(
+                rows,
+                "<td class=\"{status}\">{}</td><td class=\"{status}\">{status}</td>",
+                escape_html(answer)
+            )
+            .unwrap();
+        }
+        let elapsed = record
+            .elapsed
+            .map_or_else(|| "-".to_owned(), |elapsed| format!("{elapsed:.2?}"));
+        let mut links = format!("<a href=\"/day{:02}.json\">json</a>", record.day);
+        if day.trace.is_some() {
+            write!(
+                links,
+                " <a href=\"/day{:02}-trace.json\">trace</a>",
+                record.day
+            )
+            .unwrap();
+        }
+        if cfg!(feature = "viz") && day.render.is_some() {
+            write!(links, " <a href=\"/day{:02}.png\">png</a>", record.day).unwrap();
+        }
+        writeln!(
+            rows,
+            "<td>{elapsed}</td><td>{links}</td><td>{}</td></tr>",
+            escape_html(record.error.as_deref().unwrap_or(""))
+        )
+        .unwrap();
+    }
+    format!(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{name}</title>
+<style>
+body {{ font-family: sans-serif; }}
+td, th {{ padding: 0.2em 0.8em; text-align: left; }}
+.correct {{ color: green; }}
+.wrong, .missing {{ color: red; }}
+.unverified {{ color: gray; }}
+</style>
+</head>
+<body>
+<h1>{name}</h1>
+<form method=\"post\" action=\"/run\"><button>Run again</button> <a href=\"/results.json\">results.json</a></form>
+<table>
+<tr><th>Day</th><th>Part A</th><th></th><th>Part B</th><th></th><th>Time</th><th></th><th>Error</th></tr>
+{rows}</table>
+</body>
+</html>
+"
+    )
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status: "200 OK",
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    fn text(status: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+
+    fn write_to(&self, stream: &mut TcpStream) -> Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        )?;
+        if self.status.starts_with("303") {
+            write!(stream, "Location: /\r\n")?;
+        }
+        write!(stream, "\r\n")?;
+        stream.write_all(&self.body)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    Get,
+    Post,
+}
+
+/// Returns the method and path of a request, having read the rest of its
+/// headers. Request bodies are ignored.
+fn read_request(stream: &TcpStream) -> Result<(Method, String)> {
+    // One client that never finishes its request would otherwise hold up
+    // everyone else, as requests are handled one at a time.
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    parse_request(BufReader::new(stream))
+}
+
+fn parse_request(mut reader: impl BufRead) -> Result<(Method, String)> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+    match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["GET", path, _] => Ok((Method::Get, path.to_owned())),
+        ["POST", path, _] => Ok((Method::Post, path.to_owned())),
+        _ => bail!("Unsupported request: {}", request_line.trim()),
+    }
+}
+
+/// Picks the day out of paths like `/day07.json`.
+fn day_file(path: &str, extension: &str) -> Option<u32> {
+    path.strip_prefix("/day")?
+        .strip_suffix(extension)?
+        .parse()
+        .ok()
+}
+
+/// A day's trace as JSON, cut short if it runs past `MAX_TRACE_STEPS`.
+fn trace_json(day: &Day, trace: super::Trace, options: &Options) -> Result<String> {
+    let input_path = options.input_path(day.day);
+    let input = fs::read_to_string(&input_path)
+        .with_context(|| format!("Failed to read {}", input_path.display()))?;
+    let budget = Budget::unlimited().with_max_steps(MAX_TRACE_STEPS);
+    let steps = trace(&input, &budget)?;
+    Ok(format!(
+        "{{\"day\": {}, \"input\": {}, \"steps\": [{}]}}\n",
+        day.day,
+        json_string(&input_path.display().to_string()),
+        steps.iter().map(|step| json_string(step)).join(", ")
+    ))
+}
+
+fn route(
+    method: Method,
+    path: &str,
+    name: &str,
+    days: &[&Day],
+    records: &mut Vec<Record>,
+    options: &Options,
+) -> Result<Response> {
+    // Solving again changes what's served, so it isn't done for a GET.
+    if path == "/run" {
+        if method != Method::Post {
+            return Ok(Response::text("405 Method Not Allowed", "Use POST\n"));
+        }
+        *records = solve_days(days, options)?;
+        return Ok(Response::text("303 See Other", ""));
+    }
+    if method != Method::Get {
+        return Ok(Response::text("405 Method Not Allowed", "Use GET\n"));
+    }
+    if path == "/" {
+        return Ok(Response::ok(
+            "text/html; charset=utf-8",
+            index_page(name, days, records),
+        ));
+    }
+    if path == "/results.json" {
+        let json = format!("[{}]\n", records.iter().map(Record::to_json).join(",\n"));
+        return Ok(Response::ok("application/json", json));
+    }
+    if let Some(day) = day_file(path, "-trace.json") {
+        if let Some((day, trace)) = days
+            .iter()
+            .find(|d| d.day == day)
+            .and_then(|d| Some((d, d.trace?)))
+        {
+            return Ok(Response::ok(
+                "application/json",
+                trace_json(day, trace, options)?,
+            ));
+        }
+    }
+    if let Some(day) = day_file(path, ".json") {
+        if let Some(record) = records.iter().find(|record| record.day == day) {
+            return Ok(Response::ok("application/json", record.to_json() + "\n"));
+        }
+    }
+    if let Some(day) = day_file(path, ".png") {
+        if let Some(render) = days.iter().find(|d| d.day == day).and_then(|d| d.render) {
+            let input_path = options.input_path(day);
+            let input = fs::read_to_string(&input_path)
+                .with_context(|| format!("Failed to read {}", input_path.display()))?;
+            return Ok(Response::ok("image/png", render(&input)?.to_png()?));
+        }
+    }
+    Ok(Response::text("404 Not Found", "Not found\n"))
+}
+
+fn handle(
+    mut stream: TcpStream,
+    name: &str,
+    days: &[&Day],
+    records: &mut Vec<Record>,
+    options: &Options,
+) -> Result<()> {
+    let (method, path) = read_request(&stream)?;
+    let response = route(method, &path, name, days, records, options).unwrap_or_else(|e| {
+        println!("{path} failed: {e:#}");
+        Response::text("500 Internal Server Error", format!("{e:#}\n"))
+    });
+    response.write_to(&mut stream)
+}
+
+/// Solves the days, then serves their results on localhost until killed.
+/// Requests are handled one at a time, and a POST to `/run` solves the days
+/// again.
+pub fn serve(name: &str, days: &[&Day], options: &Options) -> Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, options.port))
+        .with_context(|| format!("Failed to listen on port {}", options.port))?;
+    let mut records = solve_days(days, options)?;
+    println!("Serving on http://{}/", listener.local_addr()?);
+    for stream in listener.incoming() {
+        if let Err(e) = stream
+            .map_err(Into::into)
+            .and_then(|stream| handle(stream, name, days, &mut records, options))
+        {
+            println!("Request failed: {e:#}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_requests() {
+        let request = "GET /day07.json HTTP/1.1\r\nHost: localhost\r\nAccept: */*\r\n\r\n";
+        assert_eq!(
+            parse_request(request.as_bytes()).unwrap(),
+            (Method::Get, "/day07.json".to_owned())
+        );
+        let request = "POST /run HTTP/1.1\r\nContent-Length: 0\r\n\r\n";
+        assert_eq!(
+            parse_request(request.as_bytes()).unwrap(),
+            (Method::Post, "/run".to_owned())
+        );
+        // Headers may end with the connection.
+        assert_eq!(
+            parse_request("GET / HTTP/1.0\r\n".as_bytes()).unwrap(),
+            (Method::Get, "/".to_owned())
+        );
+        assert!(parse_request("DELETE / HTTP/1.1\r\n\r\n".as_bytes()).is_err());
+        assert!(parse_request("GET /\r\n\r\n".as_bytes()).is_err());
+        assert!(parse_request("".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(json_string("a \"b\" \\ c"), r#""a \"b\" \\ c""#);
+        assert_eq!(json_string("1\n2\t3\u{1}"), r#""1\n2\u00093\u0001""#);
+        assert_eq!(json_string("é ✓"), "\"é ✓\"");
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">&amp;</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;amp;&lt;/a&gt;"
+        );
+        assert_eq!(escape_html("1,2"), "1,2");
+    }
+
+    #[test]
+    fn picks_out_day_files() {
+        assert_eq!(day_file("/day07.json", ".json"), Some(7));
+        assert_eq!(day_file("/day25.png", ".png"), Some(25));
+        assert_eq!(day_file("/day06-trace.json", "-trace.json"), Some(6));
+        assert_eq!(day_file("/day06-trace.json", ".json"), None);
+        assert_eq!(day_file("/day07.png", ".json"), None);
+        assert_eq!(day_file("/dayx.json", ".json"), None);
+        assert_eq!(day_file("/results.json", ".json"), None);
+    }
+}
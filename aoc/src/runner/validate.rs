@@ -0,0 +1,40 @@
+use super::{Day, Options};
+use anyhow::{ensure, Context, Result};
+use std::fs;
+
+/// Checks each day's input against the assumptions its solver makes, reporting
+/// every violated assumption rather than stopping at the first.
+pub fn validate(days: &[&Day], options: &Options) -> Result<()> {
+    let mut failed = Vec::new();
+    for day in days {
+        let Some(validate) = day.validate else {
+            println!("Day {:02}: no validator", day.day);
+            continue;
+        };
+        let path = options.input_path(day.day);
+        let violations = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))
+            .and_then(|input| validate(&input));
+        match violations {
+            Ok(violations) if violations.is_empty() => println!("Day {:02}: ok", day.day),
+            Ok(violations) => {
+                let plural = if violations.len() == 1 { "" } else { "s" };
+                println!(
+                    "Day {:02}: {} assumption{plural} violated",
+                    day.day,
+                    violations.len()
+                );
+                for violation in violations {
+                    println!("  {violation}");
+                }
+                failed.push(day.day);
+            }
+            Err(e) => {
+                println!("Day {:02} failed: {e:#}", day.day);
+                failed.push(day.day);
+            }
+        }
+    }
+    ensure!(failed.is_empty(), "Invalid inputs for days: {failed:?}");
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use super::{solve_input, Answers, Day, Options, Part};
+use std::{fs, path::PathBuf, thread};
+
+struct Watched {
+    path: PathBuf,
+    contents: Option<String>,
+    answers: Option<Answers>,
+    missing: bool,
+}
+
+fn print_diff(previous: Option<&Answers>, answers: &Answers) {
+    for part in [Part::A, Part::B] {
+        let answer = answers[part.index()].as_deref().unwrap_or("-");
+        match previous.map(|previous| previous[part.index()].as_deref().unwrap_or("-")) {
+            None => println!("  part {part}: {answer}"),
+            Some(previous) if previous == answer => println!("  part {part}: {answer} (unchanged)"),
+            Some(previous) => println!("  part {part}: {answer} (was {previous})"),
+        }
+    }
+}
+
+/// Polls the watched files, re-solving each one whenever its contents change.
+/// Errors are reported and watching carries on, so a half-edited input doesn't
+/// end the session.
+pub fn watch(day: &Day, options: &Options) -> anyhow::Result<()> {
+    let paths = if options.files.is_empty() {
+        vec![options.input_path(day.day)]
+    } else {
+        options.files.clone()
+    };
+    let mut watched = paths
+        .into_iter()
+        .map(|path| Watched {
+            path,
+            contents: None,
+            answers: None,
+            missing: false,
+        })
+        .collect::<Vec<_>>();
+
+    println!("Watching day {:02}, press Ctrl-C to stop", day.day);
+    loop {
+        for file in &mut watched {
+            let contents = match fs::read_to_string(&file.path) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    if !file.missing {
+                        println!("{}: {e}", file.path.display());
+                        file.missing = true;
+                    }
+                    file.contents = None;
+                    continue;
+                }
+            };
+            file.missing = false;
+            if file.contents.as_ref() == Some(&contents) {
+                continue;
+            }
+
+            println!("{}:", file.path.display());
+            let puzzle_input = file.path == options.input_path(day.day);
+            match solve_input(day, &contents, puzzle_input, options) {
+                Ok(solved) => {
+                    print_diff(file.answers.as_ref(), &solved.answers);
+                    for violation in &solved.skipped {
+                        println!("  {violation}");
+                    }
+                    for line in &solved.explanation {
+                        println!("  {line}");
+                    }
+                    println!("  took {:.2?}", solved.elapsed);
+                    for line in &solved.stats {
+                        println!("  {line}");
+                    }
+                    file.answers = Some(solved.answers);
+                }
+                Err(e) => println!("  error: {e:#}"),
+            }
+            file.contents = Some(contents);
+        }
+        thread::sleep(options.interval);
+    }
+}
@@ -0,0 +1,75 @@
+use rustc_hash::FxHashMap as HashMap;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+};
+
+/// Finds the cost of the cheapest path from `start` to a node satisfying
+/// `is_goal`. `neighbours` gives each node's successors and the cost of the
+/// step to them, and `heuristic` must never overestimate the remaining cost.
+/// Returns `None` if no goal is reachable.
+pub fn astar<N, I>(
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> u32,
+) -> Option<u32>
+where
+    N: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, u32)>,
+{
+    // Among equally promising nodes, the one furthest along is tried first.
+    let mut open = BinaryHeap::new();
+    let mut costs: HashMap<N, u32> = HashMap::default();
+    open.push((Reverse(heuristic(&start)), 0, start));
+
+    while let Some((_, g, node)) = open.pop() {
+        if is_goal(&node) {
+            return Some(g);
+        }
+        if costs.get(&node).is_some_and(|cost| g >= *cost) {
+            continue;
+        }
+        costs.insert(node.clone(), g);
+        for (next, step) in neighbours(&node) {
+            let next_g = g + step;
+            open.push((Reverse(next_g + heuristic(&next)), next_g, next));
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm, as `astar` without a heuristic.
+pub fn dijkstra<N, I>(
+    start: N,
+    is_goal: impl FnMut(&N) -> bool,
+    neighbours: impl FnMut(&N) -> I,
+) -> Option<u32>
+where
+    N: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, u32)>,
+{
+    astar(start, is_goal, neighbours, |_| 0)
+}
+
+/// The number of steps from `start` to every node reachable from it.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::default();
+    distances.insert(start.clone(), 0);
+    let mut open = VecDeque::from([start]);
+    while let Some(node) = open.pop_front() {
+        let distance = distances[&node];
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                open.push_back(next);
+            }
+        }
+    }
+    distances
+}
@@ -0,0 +1,299 @@
+use crate::budget::Budget;
+use anyhow::Result;
+
+/// Something that evolves one step at a time, such as a guard walking a map.
+pub trait Simulation {
+    type State;
+
+    fn state(&self) -> &Self::State;
+
+    /// Advances by one step. Never called once the simulation is done.
+    fn step(&mut self);
+
+    /// Whether the simulation has finished. Simulations that run forever
+    /// needn't implement this.
+    fn is_done(&self) -> bool {
+        false
+    }
+}
+
+/// Where a simulation's states start repeating, and how often.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+/// Steps the simulation `n` times, or until it's done if that's sooner.
+/// Returns how many steps were taken.
+pub fn run<S: Simulation>(sim: &mut S, n: usize, budget: &Budget) -> Result<usize> {
+    for i in 0..n {
+        if sim.is_done() {
+            return Ok(i);
+        }
+        budget.step()?;
+        sim.step();
+    }
+    Ok(n)
+}
+
+/// Steps the simulation until it's done, returning how many steps were taken.
+pub fn run_until_done<S: Simulation>(sim: &mut S, budget: &Budget) -> Result<usize> {
+    trace(sim, budget, |_| {})
+}
+
+/// Like `run_until_done`, but passes `visit` every state before stepping on
+/// from it. The final state isn't visited.
+pub fn trace<S: Simulation>(
+    sim: &mut S,
+    budget: &Budget,
+    mut visit: impl FnMut(&S::State),
+) -> Result<usize> {
+    let mut steps = 0;
+    while !sim.is_done() {
+        budget.step()?;
+        visit(sim.state());
+        sim.step();
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+/// Finds the cycle the simulation's states fall into using Brent's algorithm,
+/// which keeps two copies of the simulation rather than every state seen.
+/// Returns `None` if the simulation finishes instead.
+pub fn find_cycle<S>(sim: &S, budget: &Budget) -> Result<Option<Cycle>>
+where
+    S: Simulation + Clone,
+    S::State: Eq,
+{
+    let mut tortoise = sim.clone();
+    let mut hare = sim.clone();
+    let mut power = 1;
+    let mut period = 0;
+    loop {
+        if hare.is_done() {
+            return Ok(None);
+        }
+        budget.step()?;
+        hare.step();
+        period += 1;
+        if tortoise.state() == hare.state() {
+            break;
+        }
+        if period == power {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+    }
+
+    let mut tortoise = sim.clone();
+    let mut hare = sim.clone();
+    run(&mut hare, period, budget)?;
+    let mut start = 0;
+    while tortoise.state() != hare.state() {
+        budget.step()?;
+        tortoise.step();
+        hare.step();
+        start += 1;
+    }
+    Ok(Some(Cycle { start, period }))
+}
+
+/// Finds the same cycle as `find_cycle` using Floyd's algorithm, which steps
+/// a hare at twice the speed of a tortoise. Usually slower than Brent's.
+pub fn find_cycle_floyd<S>(sim: &S, budget: &Budget) -> Result<Option<Cycle>>
+where
+    S: Simulation + Clone,
+    S::State: Eq,
+{
+    let mut tortoise = sim.clone();
+    let mut hare = sim.clone();
+    loop {
+        if run(&mut hare, 2, budget)? < 2 {
+            return Ok(None);
+        }
+        budget.step()?;
+        tortoise.step();
+        if tortoise.state() == hare.state() {
+            break;
+        }
+    }
+
+    let mut tortoise = sim.clone();
+    let mut start = 0;
+    while tortoise.state() != hare.state() {
+        budget.step()?;
+        tortoise.step();
+        hare.step();
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = tortoise.clone();
+    hare.step();
+    while tortoise.state() != hare.state() {
+        budget.step()?;
+        hare.step();
+        period += 1;
+    }
+    Ok(Some(Cycle { start, period }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::BudgetExceeded;
+    use rustc_hash::FxHashMap as HashMap;
+
+    /// Walks a functional graph from node to node. `next[i]` is where node
+    /// `i` leads, and a node past the end of `next` finishes the walk.
+    #[derive(Debug, Clone)]
+    struct Walk<'a> {
+        next: &'a [usize],
+        node: usize,
+    }
+
+    impl Simulation for Walk<'_> {
+        type State = usize;
+
+        fn state(&self) -> &usize {
+            &self.node
+        }
+
+        fn step(&mut self) {
+            self.node = self.next[self.node];
+        }
+
+        fn is_done(&self) -> bool {
+            self.node >= self.next.len()
+        }
+    }
+
+    /// The cycle found by remembering every state.
+    fn brute_force(walk: &Walk) -> Option<Cycle> {
+        let mut seen = HashMap::default();
+        let mut walk = walk.clone();
+        for steps in 0.. {
+            if walk.is_done() {
+                return None;
+            }
+            if let Some(start) = seen.insert(walk.node, steps) {
+                return Some(Cycle {
+                    start,
+                    period: steps - start,
+                });
+            }
+            walk.step();
+        }
+        unreachable!()
+    }
+
+    /// Every graph on `n` nodes where each node leads to another node or to
+    /// the end.
+    fn graphs(n: usize) -> impl Iterator<Item = Vec<usize>> {
+        (0..(n + 1).pow(n as u32)).map(move |mut code| {
+            (0..n)
+                .map(|_| {
+                    let next = code % (n + 1);
+                    code /= n + 1;
+                    next
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn finds_cycles_like_brute_force() {
+        let budget = Budget::unlimited();
+        for n in 1..=5 {
+            for next in graphs(n) {
+                for node in 0..n {
+                    let walk = Walk { next: &next, node };
+                    let expected = brute_force(&walk);
+                    assert_eq!(find_cycle(&walk, &budget).unwrap(), expected, "{next:?}");
+                    assert_eq!(
+                        find_cycle_floyd(&walk, &budget).unwrap(),
+                        expected,
+                        "{next:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_long_cycles() {
+        // A tail of 1000 nodes into a loop of 777.
+        let next = (1..1777).chain([1000]).collect::<Vec<_>>();
+        let walk = Walk {
+            next: &next,
+            node: 0,
+        };
+        let expected = Some(Cycle {
+            start: 1000,
+            period: 777,
+        });
+        let budget = Budget::unlimited();
+        assert_eq!(find_cycle(&walk, &budget).unwrap(), expected);
+        assert_eq!(find_cycle_floyd(&walk, &budget).unwrap(), expected);
+
+        let budget = Budget::unlimited().with_max_steps(100);
+        assert!(find_cycle(&walk, &budget).is_err());
+    }
+
+    #[test]
+    fn runs_until_done() {
+        let next = [1, 2, 3, 4];
+        let budget = Budget::unlimited();
+
+        let mut walk = Walk {
+            next: &next,
+            node: 0,
+        };
+        assert_eq!(run(&mut walk, 2, &budget).unwrap(), 2);
+        assert_eq!(walk.node, 2);
+        // Stops early once done.
+        assert_eq!(run(&mut walk, 10, &budget).unwrap(), 2);
+        assert_eq!(walk.node, 4);
+        assert_eq!(run(&mut walk, 10, &budget).unwrap(), 0);
+
+        let mut walk = Walk {
+            next: &next,
+            node: 1,
+        };
+        assert_eq!(run_until_done(&mut walk, &budget).unwrap(), 3);
+        assert!(walk.is_done());
+    }
+
+    #[test]
+    fn traces_every_state_but_the_last() {
+        let next = [2, 4, 3, 1];
+        let mut walk = Walk {
+            next: &next,
+            node: 0,
+        };
+        let mut visited = Vec::new();
+        let steps = trace(&mut walk, &Budget::unlimited(), |&node| visited.push(node)).unwrap();
+        assert_eq!(steps, 4);
+        assert_eq!(visited, [0, 2, 3, 1]);
+        assert_eq!(walk.node, 4);
+    }
+
+    #[test]
+    fn stops_when_the_budget_runs_out() {
+        let next = [0];
+        let budget = Budget::unlimited().with_max_steps(5);
+        let mut walk = Walk {
+            next: &next,
+            node: 0,
+        };
+        let error = run_until_done(&mut walk, &budget).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<BudgetExceeded>(),
+            Some(&BudgetExceeded::Steps(5))
+        );
+        assert_eq!(budget.steps_taken(), 6);
+    }
+}
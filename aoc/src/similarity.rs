@@ -0,0 +1,443 @@
+use itertools::Itertools;
+use rustc_hash::FxHashMap as HashMap;
+use std::collections::BTreeMap;
+
+/// Which of the two lists an ID is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    fn index(self) -> usize {
+        match self {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+}
+
+/// The fewest distinct IDs in a block of a `SimilarityIndex`.
+const MIN_BLOCK_SIZE: usize = 32;
+
+/// A run of consecutive distinct IDs in a `SimilarityIndex`. Each ID has a
+/// balance, how many more left IDs than right ones there are up to and
+/// including it, stored less `offset` so that every balance in the block can
+/// be shifted at once.
+#[derive(Debug, Clone, Default)]
+struct Block {
+    ids: Vec<i64>,
+    balances: Vec<i64>,
+    /// The gap from each ID to the next one, or zero for the last ID overall.
+    gaps: Vec<u64>,
+    offset: i64,
+    /// The total gap at each stored balance.
+    gaps_by_balance: HashMap<i64, u64>,
+    /// The sum of each gap times the absolute value of its balance.
+    area: u64,
+    /// The total gap with a balance of at least zero.
+    non_negative: u64,
+    total: u64,
+}
+
+impl Block {
+    fn new(entries: impl IntoIterator<Item = (i64, i64, u64)>) -> Self {
+        let mut block = Self::default();
+        for (id, balance, gap) in entries {
+            block.ids.push(id);
+            block.balances.push(balance);
+            block.gaps.push(gap);
+            block.tally(balance, gap, true);
+        }
+        block
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn balance(&self, i: usize) -> i64 {
+        self.balances[i] + self.offset
+    }
+
+    fn entries(&self) -> impl Iterator<Item = (i64, i64, u64)> + '_ {
+        (0..self.len()).map(|i| (self.ids[i], self.balance(i), self.gaps[i]))
+    }
+
+    /// Adds or takes away a gap at a stored balance from the totals.
+    fn tally(&mut self, balance: i64, gap: u64, add: bool) {
+        if gap == 0 {
+            return;
+        }
+        let actual = balance + self.offset;
+        let area = gap * actual.unsigned_abs();
+        let non_negative = if actual >= 0 { gap } else { 0 };
+        if add {
+            *self.gaps_by_balance.entry(balance).or_default() += gap;
+            self.area += area;
+            self.non_negative += non_negative;
+            self.total += gap;
+        } else {
+            let total = self.gaps_by_balance.get_mut(&balance).unwrap();
+            *total -= gap;
+            if *total == 0 {
+                self.gaps_by_balance.remove(&balance);
+            }
+            self.area -= area;
+            self.non_negative -= non_negative;
+            self.total -= gap;
+        }
+    }
+
+    fn set(&mut self, i: usize, balance: i64, gap: u64) {
+        self.tally(self.balances[i], self.gaps[i], false);
+        self.balances[i] = balance - self.offset;
+        self.gaps[i] = gap;
+        self.tally(self.balances[i], gap, true);
+    }
+
+    fn insert(&mut self, i: usize, id: i64, balance: i64, gap: u64) {
+        self.ids.insert(i, id);
+        self.balances.insert(i, balance - self.offset);
+        self.gaps.insert(i, gap);
+        self.tally(balance - self.offset, gap, true);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.tally(self.balances[i], self.gaps[i], false);
+        self.ids.remove(i);
+        self.balances.remove(i);
+        self.gaps.remove(i);
+    }
+
+    /// Adds one to every balance in the block, or takes one away.
+    fn shift(&mut self, up: bool) {
+        let at = |balance: i64| self.gaps_by_balance.get(&balance).copied().unwrap_or(0);
+        if up {
+            // Balances of zero and above grow, negative ones shrink towards
+            // zero, and those at -1 reach it.
+            self.area = self.area + self.non_negative - (self.total - self.non_negative);
+            self.non_negative += at(-1 - self.offset);
+            self.offset += 1;
+        } else {
+            let zero = at(-self.offset);
+            let positive = self.non_negative - zero;
+            self.area = self.area + (self.total - positive) - positive;
+            self.non_negative -= zero;
+            self.offset -= 1;
+        }
+    }
+}
+
+/// Both lists kept sorted with a count for each ID, for reconciling lists
+/// that change over time. Inserting or removing an ID takes O(√n) time for n
+/// distinct IDs, as do the distance and similarity score.
+///
+/// The distance is kept as the area between the lists' cumulative counts,
+/// which is the same as pairing the lists up in sorted order. Adding a left ID
+/// raises the difference between the counts for every ID from it upwards, so
+/// the distinct IDs are split into blocks of about √n that can each be raised
+/// at once.
+#[derive(Debug, Clone, Default)]
+pub struct SimilarityIndex {
+    counts: BTreeMap<i64, [u64; 2]>,
+    lens: [usize; 2],
+    similarity: i64,
+    blocks: Vec<Block>,
+}
+
+impl SimilarityIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_lists(left: &[i64], right: &[i64]) -> Self {
+        let mut index = Self::new();
+        for (side, list) in [(Side::Left, left), (Side::Right, right)] {
+            for &id in list {
+                index.counts.entry(id).or_default()[side.index()] += 1;
+            }
+        }
+        index.lens = [left.len(), right.len()];
+        index.similarity = index
+            .counts
+            .iter()
+            .map(|(&id, counts)| id * (counts[0] * counts[1]) as i64)
+            .sum();
+        index.rebuild();
+        index
+    }
+
+    pub fn len(&self, side: Side) -> usize {
+        self.lens[side.index()]
+    }
+
+    /// How many distinct IDs are in either list.
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// How many times `id` appears in one list.
+    pub fn count(&self, side: Side, id: i64) -> u64 {
+        self.counts
+            .get(&id)
+            .map_or(0, |counts| counts[side.index()])
+    }
+
+    /// One list's IDs in ascending order, with how many times each appears.
+    pub fn iter(&self, side: Side) -> impl Iterator<Item = (i64, u64)> + '_ {
+        self.counts
+            .iter()
+            .map(move |(&id, counts)| (id, counts[side.index()]))
+            .filter(|(_, count)| *count > 0)
+    }
+
+    /// The total distance between the lists, or `None` if they're different
+    /// lengths and can't be paired up.
+    pub fn distance(&self) -> Option<u64> {
+        (self.lens[0] == self.lens[1]).then(|| self.blocks.iter().map(|block| block.area).sum())
+    }
+
+    pub fn similarity(&self) -> i64 {
+        self.similarity
+    }
+
+    pub fn insert(&mut self, side: Side, id: i64) {
+        let counts = self.counts.entry(id).or_default();
+        let new = *counts == [0, 0];
+        counts[side.index()] += 1;
+        self.similarity += id * counts[1 - side.index()] as i64;
+        self.lens[side.index()] += 1;
+
+        if new {
+            self.insert_id(id);
+        }
+        let (b, i) = self.locate(id).unwrap();
+        self.raise_from(b, i, side == Side::Left);
+        self.rebalance();
+    }
+
+    /// Removes one copy of `id`, returning whether there was one.
+    pub fn remove(&mut self, side: Side, id: i64) -> bool {
+        let Some(counts) = self.counts.get_mut(&id) else {
+            return false;
+        };
+        if counts[side.index()] == 0 {
+            return false;
+        }
+        counts[side.index()] -= 1;
+        self.similarity -= id * counts[1 - side.index()] as i64;
+        self.lens[side.index()] -= 1;
+        let unused = *counts == [0, 0];
+
+        let (b, i) = self.locate(id).unwrap();
+        self.raise_from(b, i, side == Side::Right);
+        if unused {
+            self.counts.remove(&id);
+            self.remove_id(b, i);
+        }
+        self.rebalance();
+        true
+    }
+
+    /// Finds the block holding `id` and where it is in the block, or where it
+    /// would go.
+    fn locate(&self, id: i64) -> Result<(usize, usize), (usize, usize)> {
+        if self.blocks.is_empty() {
+            return Err((0, 0));
+        }
+        let b = self
+            .blocks
+            .partition_point(|block| *block.ids.last().unwrap() < id)
+            .min(self.blocks.len() - 1);
+        match self.blocks[b].ids.binary_search(&id) {
+            Ok(i) => Ok((b, i)),
+            Err(i) => Err((b, i)),
+        }
+    }
+
+    /// The position before `(b, i)`, if there is one.
+    fn previous(&self, b: usize, i: usize) -> Option<(usize, usize)> {
+        if i > 0 {
+            Some((b, i - 1))
+        } else {
+            b.checked_sub(1).map(|b| (b, self.blocks[b].len() - 1))
+        }
+    }
+
+    /// Raises or lowers the balance of every ID from `(b, i)` upwards.
+    fn raise_from(&mut self, b: usize, i: usize, up: bool) {
+        let delta = if up { 1 } else { -1 };
+        let block = &mut self.blocks[b];
+        for j in i..block.len() {
+            block.set(j, block.balance(j) + delta, block.gaps[j]);
+        }
+        for block in &mut self.blocks[b + 1..] {
+            block.shift(up);
+        }
+    }
+
+    /// Adds a distinct ID with no copies in either list yet.
+    fn insert_id(&mut self, id: i64) {
+        let (b, i) = self.locate(id).unwrap_err();
+        if self.blocks.is_empty() {
+            self.blocks.push(Block::new([(id, 0, 0)]));
+            return;
+        }
+        let next = if i < self.blocks[b].len() {
+            Some(self.blocks[b].ids[i])
+        } else {
+            self.blocks.get(b + 1).map(|block| block.ids[0])
+        };
+        let balance = match self.previous(b, i) {
+            Some((pb, pi)) => {
+                let previous = &mut self.blocks[pb];
+                let balance = previous.balance(pi);
+                previous.set(pi, balance, previous.ids[pi].abs_diff(id));
+                balance
+            }
+            None => 0,
+        };
+        let gap = next.map_or(0, |next| next.abs_diff(id));
+        self.blocks[b].insert(i, id, balance, gap);
+
+        if self.blocks[b].len() > 2 * self.block_size() {
+            let block = &mut self.blocks[b];
+            let half = block.entries().skip(block.len() / 2).collect_vec();
+            *block = Block::new(block.entries().take(block.len() / 2).collect_vec());
+            self.blocks.insert(b + 1, Block::new(half));
+        }
+    }
+
+    /// Drops the distinct ID at `(b, i)`, which no longer has any copies.
+    fn remove_id(&mut self, b: usize, i: usize) {
+        let previous = self.previous(b, i);
+        let next = if i + 1 < self.blocks[b].len() {
+            Some(self.blocks[b].ids[i + 1])
+        } else {
+            self.blocks.get(b + 1).map(|block| block.ids[0])
+        };
+        self.blocks[b].remove(i);
+        if let Some((pb, pi)) = previous {
+            let previous = &mut self.blocks[pb];
+            let gap = next.map_or(0, |next| next.abs_diff(previous.ids[pi]));
+            previous.set(pi, previous.balance(pi), gap);
+        }
+        if self.blocks[b].len() == 0 {
+            self.blocks.remove(b);
+        }
+    }
+
+    fn block_size(&self) -> usize {
+        (self.counts.len() as f64).sqrt().max(MIN_BLOCK_SIZE as f64) as usize
+    }
+
+    /// Rebuilds the blocks if splits or removals have left too many of them.
+    fn rebalance(&mut self) {
+        if self.blocks.len() > 2 * self.counts.len().div_ceil(self.block_size()) + 1 {
+            self.rebuild();
+        }
+    }
+
+    /// Rebuilds the blocks from the counts.
+    fn rebuild(&mut self) {
+        let mut balance = 0;
+        let entries = self
+            .counts
+            .iter()
+            .map(|(&id, counts)| {
+                balance += counts[0] as i64 - counts[1] as i64;
+                (id, balance)
+            })
+            .collect_vec();
+        let size = self.block_size();
+        self.blocks = entries
+            .iter()
+            .enumerate()
+            .map(|(i, &(id, balance))| {
+                let gap = entries.get(i + 1).map_or(0, |next| next.0.abs_diff(id));
+                (id, balance, gap)
+            })
+            .chunks(size)
+            .into_iter()
+            .map(Block::new)
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Rng;
+
+    fn brute_force(left: &[i64], right: &[i64]) -> (Option<u64>, i64) {
+        let distance = (left.len() == right.len()).then(|| {
+            let (left, right) = (left.iter().sorted(), right.iter().sorted());
+            left.zip(right).map(|(a, b)| a.abs_diff(*b)).sum()
+        });
+        let similarity = left
+            .iter()
+            .map(|a| a * right.iter().filter(|b| *b == a).count() as i64)
+            .sum();
+        (distance, similarity)
+    }
+
+    fn check(index: &SimilarityIndex, lists: &[Vec<i64>; 2]) {
+        let [left, right] = lists;
+        assert_eq!(
+            (index.distance(), index.similarity()),
+            brute_force(left, right)
+        );
+        for side in [Side::Left, Side::Right] {
+            let list = &lists[side.index()];
+            assert_eq!(index.len(side), list.len());
+            let counts = list.iter().copied().counts();
+            let expected = counts.into_iter().map(|(id, n)| (id, n as u64)).sorted();
+            assert!(index.iter(side).eq(expected));
+        }
+    }
+
+    #[test]
+    fn similarity_index_matches_brute_force() {
+        let mut rng = Rng::new(2);
+        // A narrow range of IDs gives plenty of repeats, and a wide one
+        // enough distinct IDs to split and merge blocks.
+        for range in [10, 1000] {
+            let mut index = SimilarityIndex::new();
+            let mut lists = [Vec::new(), Vec::new()];
+            for step in 0..3000 {
+                let side = if rng.chance() {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+                let list = &mut lists[side.index()];
+                // Lean towards inserting early on, so the lists grow before
+                // shrinking again.
+                if rng.below(3000) < 1800 - step / 3 {
+                    let id = rng.between(-range, range);
+                    index.insert(side, id);
+                    list.push(id);
+                } else if !list.is_empty() && rng.below(4) != 0 {
+                    let id = list.swap_remove(rng.below(list.len()));
+                    assert!(index.remove(side, id));
+                } else {
+                    let id = rng.between(-range, range);
+                    assert_eq!(index.remove(side, id), list.contains(&id));
+                    if let Some(i) = list.iter().position(|&other| other == id) {
+                        list.swap_remove(i);
+                    }
+                }
+                check(&index, &lists);
+                if step % 100 == 0 {
+                    check(&SimilarityIndex::from_lists(&lists[0], &lists[1]), &lists);
+                }
+            }
+        }
+    }
+}
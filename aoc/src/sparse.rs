@@ -0,0 +1,207 @@
+use crate::grid::Pos;
+use anyhow::Result;
+use rustc_hash::FxHashMap as HashMap;
+
+/// A grid with no fixed size, holding only the cells that have been set. Any
+/// position is allowed, including negative ones, and the grid keeps track of
+/// the smallest rectangle containing every cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    bounds: Option<(Pos, Pos)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::default(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses one row per line starting from (0, 0), keeping the cells that
+    /// `f` turns a character into.
+    pub fn parse(input: &str, mut f: impl FnMut(Pos, char) -> Result<Option<T>>) -> Result<Self> {
+        let mut grid = Self::new();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let pos = (x as isize, y as isize);
+                if let Some(cell) = f(pos, c)? {
+                    grid.insert(pos, cell);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    /// The top left and bottom right cells of the smallest rectangle holding
+    /// every cell, or `None` if the grid is empty.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    /// Sets the cell at `pos`, returning what was there before.
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                (min.0.min(pos.0), min.1.min(pos.1)),
+                (max.0.max(pos.0), max.1.max(pos.1)),
+            ),
+            None => (pos, pos),
+        });
+        self.cells.insert(pos, value)
+    }
+
+    /// Clears the cell at `pos`, returning what was there. The bounds shrink
+    /// if it was on their edge.
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+        if let Some((min, max)) = self.bounds {
+            if pos.0 == min.0 || pos.1 == min.1 || pos.0 == max.0 || pos.1 == max.1 {
+                self.bounds = self.cells.keys().fold(None, |bounds, &pos| {
+                    Some(match bounds {
+                        Some((min, max)) => (
+                            (pos.0.min(min.0), pos.1.min(min.1)),
+                            (pos.0.max(max.0), pos.1.max(max.1)),
+                        ),
+                        None => (pos, pos),
+                    })
+                });
+            }
+        }
+        Some(value)
+    }
+
+    /// Every cell that's set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(pos, cell)| (*pos, cell))
+    }
+
+    /// Prints the bounds one row per line, turning each cell into a character
+    /// with `f` and showing unset cells as `empty`.
+    pub fn to_text(&self, empty: char, f: impl Fn(&T) -> char) -> String {
+        let mut text = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in min.1..=max.1 {
+                for x in min.0..=max.0 {
+                    text.push(self.get((x, y)).map_or(empty, &f));
+                }
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        for (pos, cell) in iter {
+            grid.insert(pos, cell);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+
+    #[test]
+    fn bounds_grow_on_insert() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.insert((2, 3), 'a'), None);
+        assert_eq!(grid.bounds(), Some(((2, 3), (2, 3))));
+        grid.insert((-4, 5), 'b');
+        grid.insert((0, -1), 'c');
+        assert_eq!(grid.bounds(), Some(((-4, -1), (2, 5))));
+        // Replacing a cell returns the old one and leaves the bounds alone.
+        assert_eq!(grid.insert((0, -1), 'd'), Some('c'));
+        assert_eq!(grid.bounds(), Some(((-4, -1), (2, 5))));
+        assert_eq!(grid.len(), 3);
+    }
+
+    #[test]
+    fn bounds_shrink_on_remove() {
+        let mut grid = [(-4, 5), (0, -1), (2, 3), (1, 1)]
+            .into_iter()
+            .map(|pos| (pos, ()))
+            .collect::<SparseGrid<_>>();
+        assert_eq!(grid.bounds(), Some(((-4, -1), (2, 5))));
+        // Inside the bounds, so they stay put.
+        assert_eq!(grid.remove((1, 1)), Some(()));
+        assert_eq!(grid.bounds(), Some(((-4, -1), (2, 5))));
+        assert_eq!(grid.remove((1, 1)), None);
+        assert_eq!(grid.remove((-4, 5)), Some(()));
+        assert_eq!(grid.bounds(), Some(((0, -1), (2, 3))));
+        grid.remove((0, -1));
+        assert_eq!(grid.bounds(), Some(((2, 3), (2, 3))));
+        grid.remove((2, 3));
+        assert_eq!(grid.bounds(), None);
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn parses_from_the_origin() {
+        let grid = SparseGrid::parse("..#\n#..\n...\n.#.", |_, c| match c {
+            '#' => Ok(Some(c)),
+            '.' => Ok(None),
+            _ => bail!("Invalid input: {c}"),
+        })
+        .unwrap();
+        assert_eq!(grid.len(), 3);
+        assert!(grid.contains((2, 0)) && grid.contains((0, 1)) && grid.contains((1, 3)));
+        assert_eq!(grid.bounds(), Some(((0, 0), (2, 3))));
+
+        // Positions count from the top left of the text even when nothing is
+        // kept there.
+        let grid = SparseGrid::parse("...\n..#", |_, c| Ok((c == '#').then_some(c))).unwrap();
+        assert_eq!(grid.bounds(), Some(((2, 1), (2, 1))));
+        assert!(SparseGrid::parse("ab", |_, c| Ok((c == 'x').then_some(c)))
+            .unwrap()
+            .is_empty());
+        assert!(SparseGrid::<char>::parse("a?", |_, c| match c {
+            'a' => Ok(None),
+            _ => bail!("Invalid input: {c}"),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn prints_the_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.to_text('.', |&c| c), "");
+        grid.insert((-1, -1), 'a');
+        grid.insert((1, 0), 'b');
+        assert_eq!(grid.to_text('.', |&c| c), "a..\n..b\n");
+        assert_eq!(grid.to_text(' ', |_| '#'), "#  \n  #\n");
+    }
+}
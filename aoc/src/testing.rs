@@ -0,0 +1,35 @@
+//! Helpers shared by tests here and in the days' crates.
+
+/// A xorshift generator, so tests don't need a random number crate. The same
+/// seed always gives the same numbers.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Xorshift gets stuck at zero, so a seed of zero is replaced.
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `low..high`.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next_u64() % low.abs_diff(high)) as i64
+    }
+
+    /// A coin flip.
+    pub fn chance(&mut self) -> bool {
+        self.next_u64().is_multiple_of(2)
+    }
+}
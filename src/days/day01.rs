@@ -0,0 +1,322 @@
+use anyhow::{bail, ensure, Context, Result};
+use aoc::{
+    budget::Budget,
+    extsort::ExternalSorter,
+    runner::{Part, Run, Session},
+    similarity::{Side, SimilarityIndex},
+};
+use itertools::Itertools;
+use std::{io::BufRead, iter};
+
+/// Splits a line into IDs. Columns may be separated by commas, as in CSV, or
+/// by any amount of whitespace, which covers TSV and the puzzle's own format.
+fn parse_line(line: &str) -> Result<Vec<i64>> {
+    let fields = if line.contains(',') {
+        line.split(',').map(str::trim).collect_vec()
+    } else {
+        line.split_whitespace().collect_vec()
+    };
+    fields
+        .into_iter()
+        .map(|field| {
+            field
+                .parse()
+                .with_context(|| format!("Invalid ID: {:?}", field))
+        })
+        .collect()
+}
+
+/// Parses the input into its columns, which must all be the same length.
+fn parse_input(input: &str) -> Result<Vec<Vec<i64>>> {
+    let mut columns: Vec<Vec<i64>> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let ids = parse_line(line).with_context(|| format!("Line {}", i + 1))?;
+        if columns.is_empty() {
+            ensure!(ids.len() >= 2, "Line {} has fewer than two columns", i + 1);
+            columns.resize(ids.len(), Vec::new());
+        }
+        ensure!(
+            ids.len() == columns.len(),
+            "Line {} has {} columns rather than {}",
+            i + 1,
+            ids.len(),
+            columns.len()
+        );
+        for (column, id) in columns.iter_mut().zip(ids) {
+            column.push(id);
+        }
+    }
+    ensure!(!columns.is_empty(), "Empty input");
+    Ok(columns)
+}
+
+/// The total distance between two sorted lists, pairing them up in order.
+fn distance(
+    list_a: impl Iterator<Item = Result<i64>>,
+    list_b: impl Iterator<Item = Result<i64>>,
+) -> Result<u64> {
+    let mut distance = 0;
+    for (a, b) in list_a.zip(list_b) {
+        distance += a?.abs_diff(b?);
+    }
+    Ok(distance)
+}
+
+/// The similarity score of two sorted lists, given as each ID and how many
+/// times it appears. Both are sorted, so equal IDs line up in a single merge.
+fn similarity(
+    mut runs_a: impl Iterator<Item = Result<(i64, i64)>>,
+    mut runs_b: impl Iterator<Item = Result<(i64, i64)>>,
+) -> Result<i64> {
+    let mut similarity = 0;
+    let mut run_a = runs_a.next().transpose()?;
+    let mut run_b = runs_b.next().transpose()?;
+    while let (Some((a, count_a)), Some((b, count_b))) = (run_a, run_b) {
+        if a == b {
+            similarity += a * count_a * count_b;
+        }
+        if a <= b {
+            run_a = runs_a.next().transpose()?;
+        }
+        if b <= a {
+            run_b = runs_b.next().transpose()?;
+        }
+    }
+    Ok(similarity)
+}
+
+/// Collapses a sorted list into each ID and how many times it appears.
+fn count_runs(list: impl Iterator<Item = Result<i64>>) -> impl Iterator<Item = Result<(i64, i64)>> {
+    let mut list = list.peekable();
+    iter::from_fn(move || {
+        let value = match list.next()? {
+            Ok(value) => value,
+            Err(e) => return Some(Err(e)),
+        };
+        let mut count = 1;
+        while list
+            .next_if(|next| matches!(next, Ok(next) if *next == value))
+            .is_some()
+        {
+            count += 1;
+        }
+        Some(Ok((value, count)))
+    })
+}
+
+/// The distance and similarity score between every pair of columns, for
+/// reconciling lists from more than two sources. Both matrices are symmetric,
+/// and the puzzle's answers are at `[0][1]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrices {
+    pub distance: Vec<Vec<u64>>,
+    pub similarity: Vec<Vec<i64>>,
+}
+
+impl Matrices {
+    pub fn new(mut columns: Vec<Vec<i64>>) -> Self {
+        for column in &mut columns {
+            column.sort_unstable();
+        }
+        let runs = columns
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .dedup_with_count()
+                    .map(|(count, &id)| (id, count as i64))
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let n = columns.len();
+        let mut matrices = Self {
+            distance: vec![vec![0; n]; n],
+            similarity: vec![vec![0; n]; n],
+        };
+        for (i, j) in (0..n).tuple_combinations().chain((0..n).map(|i| (i, i))) {
+            // Nothing here can fail, as the lists are already in memory.
+            let distance = distance(
+                columns[i].iter().map(|&id| Ok(id)),
+                columns[j].iter().map(|&id| Ok(id)),
+            )
+            .unwrap();
+            let similarity = similarity(
+                runs[i].iter().map(|&run| Ok(run)),
+                runs[j].iter().map(|&run| Ok(run)),
+            )
+            .unwrap();
+            (matrices.distance[i][j], matrices.distance[j][i]) = (distance, distance);
+            (matrices.similarity[i][j], matrices.similarity[j][i]) = (similarity, similarity);
+        }
+        matrices
+    }
+
+    /// Parses and compares every column of `input`.
+    pub fn parse(input: &str) -> Result<Self> {
+        Ok(Self::new(parse_input(input)?))
+    }
+}
+
+/// Maps signed IDs onto unsigned numbers in the same order, for sorting.
+fn to_key(id: i64) -> u64 {
+    id as u64 ^ (1 << 63)
+}
+
+fn from_keys(keys: impl Iterator<Item = Result<u64>>) -> impl Iterator<Item = Result<i64>> {
+    keys.map(|key| key.map(|key| (key ^ (1 << 63)) as i64))
+}
+
+/// Solves both parts reading two columns from `input` a line at a time, for
+/// lists too big to load. Each list is sorted in runs of at most about
+/// `memory` / 2 bytes, spilled to temporary files, and the runs are merged
+/// back while computing the answers.
+pub fn solve_streaming(input: impl BufRead, memory: usize) -> Result<(u64, i64)> {
+    let mut sorter_a = ExternalSorter::new(memory / 2);
+    let mut sorter_b = ExternalSorter::new(memory / 2);
+    for (i, line) in input.lines().enumerate() {
+        let line = line.context("Failed to read input")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let ids = parse_line(&line).with_context(|| format!("Line {}", i + 1))?;
+        ensure!(
+            ids.len() == 2,
+            "Line {} has {} columns rather than 2",
+            i + 1,
+            ids.len()
+        );
+        sorter_a.push(to_key(ids[0]))?;
+        sorter_b.push(to_key(ids[1]))?;
+    }
+    ensure!(!sorter_a.is_empty(), "Empty input");
+    let list_a = sorter_a.finish()?;
+    let list_b = sorter_b.finish()?;
+
+    let part_a = distance(from_keys(list_a.iter()?), from_keys(list_b.iter()?))?;
+    let part_b = similarity(
+        count_runs(from_keys(list_a.iter()?)),
+        count_runs(from_keys(list_b.iter()?)),
+    )?;
+    Ok((part_a, part_b))
+}
+
+/// Solves the puzzle's two columns with `solve_streaming`, for the runner's
+/// `--memory` option.
+pub fn stream(input: &mut dyn BufRead, memory: usize, run: &Run) -> Result<()> {
+    let (part_a, part_b) = solve_streaming(input, memory)?;
+    run.part_a(part_a);
+    run.part_b(part_b);
+    Ok(())
+}
+
+/// The first two columns, kept in a `SimilarityIndex` so they can be changed
+/// from the REPL.
+struct Lists(SimilarityIndex);
+
+pub fn repl(input: &str) -> Result<Box<dyn Session>> {
+    let columns = parse_input(input)?;
+    Ok(Box::new(Lists(SimilarityIndex::from_lists(
+        &columns[0],
+        &columns[1],
+    ))))
+}
+
+/// `set add-left ID` and `set remove-left ID`, and the same for the right
+/// list, change the lists.
+impl Session for Lists {
+    fn show(&self) -> String {
+        format!(
+            "{} left IDs, {} right IDs, {} distinct\nDistance: {}\nSimilarity score: {}\n",
+            self.0.len(Side::Left),
+            self.0.len(Side::Right),
+            self.0.distinct(),
+            self.0
+                .distance()
+                .map_or_else(|| "lists differ in length".to_owned(), |d| d.to_string()),
+            self.0.similarity()
+        )
+    }
+
+    fn part(&self, part: Part, _budget: &Budget) -> Result<String> {
+        match part {
+            Part::A => Ok(self
+                .0
+                .distance()
+                .context("The lists differ in length")?
+                .to_string()),
+            Part::B => Ok(self.0.similarity().to_string()),
+        }
+    }
+
+    fn set(&mut self, param: &str, value: &str) -> Result<()> {
+        let id = value
+            .parse::<i64>()
+            .with_context(|| format!("Invalid ID: {value}"))?;
+        match param {
+            "add-left" => self.0.insert(Side::Left, id),
+            "add-right" => self.0.insert(Side::Right, id),
+            "remove-left" => ensure!(self.0.remove(Side::Left, id), "{id} isn't in the left list"),
+            "remove-right" => {
+                ensure!(
+                    self.0.remove(Side::Right, id),
+                    "{id} isn't in the right list"
+                )
+            }
+            _ => bail!(
+                "No such parameter: {param}. Try add-left, add-right, remove-left or remove-right"
+            ),
+        }
+        Ok(())
+    }
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let matrices = Matrices::parse(input)?;
+
+    run.part_a(matrices.distance[0][1]);
+    run.part_b(matrices.similarity[0][1]);
+
+    if matrices.distance.len() > 2 {
+        run.explain("Distances:");
+        for row in &matrices.distance {
+            run.explain(format!("  {}", row.iter().join("\t")));
+        }
+        run.explain("Similarity scores:");
+        for row in &matrices.similarity {
+            run.explain(format!("  {}", row.iter().join("\t")));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::Rng;
+
+    #[test]
+    fn streaming_matches_in_memory() {
+        let mut rng = Rng::new(1);
+        let input = (0..2000)
+            .map(|_| format!("{}   {}", rng.between(-100, 100), rng.between(-100, 100)))
+            .join("\n");
+        let matrices = Matrices::parse(&input).unwrap();
+        let expected = (matrices.distance[0][1], matrices.similarity[0][1]);
+        // Enough memory to sort in memory, and little enough to spill every
+        // few lines and merge in several passes.
+        for memory in [1 << 20, 64] {
+            assert_eq!(solve_streaming(input.as_bytes(), memory).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn streaming_rejects_bad_lines() {
+        assert!(solve_streaming("1 2\n3\n".as_bytes(), 64).is_err());
+        assert!(solve_streaming("".as_bytes(), 64).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use aoc::{parse::parse_unsigned, runner::Run};
+use itertools::Itertools;
+use nom::{
+    character::complete::{char, newline},
+    combinator::{all_consuming, opt},
+    multi::separated_list1,
+    sequence::terminated,
+    IResult,
+};
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<usize>>> {
+    all_consuming(terminated(
+        separated_list1(newline, separated_list1(char(' '), parse_unsigned)),
+        opt(newline),
+    ))(input)
+}
+
+fn is_report_safe(report: impl Iterator<Item = usize>) -> bool {
+    let mut increasing = None;
+    report.tuple_windows().all(|(a, b)| {
+        let safe_diff = (1..=3).contains(&a.abs_diff(b));
+        let safe_dir = match increasing {
+            Some(true) => b > a,
+            Some(false) => a > b,
+            None => {
+                increasing = Some(b > a);
+                true
+            }
+        };
+
+        safe_diff && safe_dir
+    })
+}
+
+/// The fewest levels to remove from `report` to make it safe, by index, or
+/// `None` if that takes more than `k`. Takes O(n·k) time for n levels.
+///
+/// For each level, and each direction, this finds the fewest levels that must
+/// go before it in a safe run ending with it. Only that number matters to the
+/// levels after, and it can't have skipped more than `k` levels to get there.
+pub fn levels_to_remove(report: &[usize], k: usize) -> Option<Vec<usize>> {
+    let n = report.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+    [true, false]
+        .into_iter()
+        .filter_map(|increasing| {
+            let is_safe_step =
+                |a: usize, b: usize| (1..=3).contains(&a.abs_diff(b)) && (b > a) == increasing;
+            // The fewest levels removed before each level, and the level
+            // kept before it.
+            let mut fewest: Vec<(usize, Option<usize>)> = Vec::with_capacity(n);
+            for j in 0..n {
+                let mut best = (j, None);
+                for i in j.saturating_sub(k.saturating_add(1))..j {
+                    let removed = fewest[i].0 + (j - i - 1);
+                    if removed < best.0 && is_safe_step(report[i], report[j]) {
+                        best = (removed, Some(i));
+                    }
+                }
+                fewest.push(best);
+            }
+
+            let (last, removed) = (0..n)
+                .map(|j| (j, fewest[j].0 + (n - 1 - j)))
+                .min_by_key(|(_, removed)| *removed)?;
+            (removed <= k).then(|| {
+                let mut kept = vec![false; n];
+                let mut level = Some(last);
+                while let Some(j) = level {
+                    kept[j] = true;
+                    level = fewest[j].1;
+                }
+                (0..n).filter(|&i| !kept[i]).collect_vec()
+            })
+        })
+        .min_by_key(|removed| removed.len())
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let reports = parse_input(input).map_err(|e| e.to_owned())?.1;
+
+    let part_a = reports
+        .iter()
+        .filter(|report| is_report_safe(report.iter().copied()))
+        .count();
+    run.part_a(part_a);
+
+    let part_b = reports
+        .iter()
+        .filter(|report| levels_to_remove(report, 1).is_some())
+        .count();
+    run.part_b(part_b);
+
+    if run.explaining() {
+        for (i, report) in reports.iter().enumerate() {
+            if is_report_safe(report.iter().copied()) {
+                continue;
+            }
+            let removed = levels_to_remove(report, report.len()).unwrap_or_default();
+            match removed[..] {
+                [n] => run.explain(format!(
+                    "Report {} ({}) is safe without level {} ({})",
+                    i + 1,
+                    report.iter().join(" "),
+                    n + 1,
+                    report[n]
+                )),
+                _ => run.explain(format!(
+                    "Report {} ({}) is unsafe whichever level is removed, but safe without levels {}",
+                    i + 1,
+                    report.iter().join(" "),
+                    removed
+                        .iter()
+                        .map(|&n| format!("{} ({})", n + 1, report[n]))
+                        .join(", ")
+                )),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc::testing::Rng;
+
+    /// The fewest levels to remove, trying every subset of them.
+    fn fewest_removed(report: &[usize]) -> usize {
+        (0..report.len())
+            .flat_map(|len| (0..report.len()).combinations(len))
+            .find(|removed| {
+                let kept = (0..report.len()).filter(|i| !removed.contains(i));
+                is_report_safe(kept.map(|i| report[i]))
+            })
+            .map_or(report.len(), |removed| removed.len())
+    }
+
+    #[test]
+    fn removes_fewest_levels() {
+        let mut rng = Rng::new(1);
+        for _ in 0..500 {
+            let report = (0..rng.below(10)).map(|_| rng.below(12)).collect_vec();
+            let fewest = fewest_removed(&report);
+            for k in (0..=report.len() + 1).chain([usize::MAX]) {
+                let removed = levels_to_remove(&report, k);
+                assert_eq!(
+                    removed.as_ref().map(Vec::len),
+                    (fewest <= k).then_some(fewest),
+                    "{report:?} with k = {k}"
+                );
+                if let Some(removed) = removed {
+                    let kept = (0..report.len()).filter(|i| !removed.contains(i));
+                    assert!(is_report_safe(kept.map(|i| report[i])));
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use aoc2024::util::parse_unsigned;
+use aoc::{parse::parse_unsigned, runner::Run};
 use nom::{
     branch::alt,
     bytes::complete::{tag, take},
@@ -45,9 +45,8 @@ fn parse_input(input: &str) -> IResult<&str, Vec<Instr>> {
     )(input)
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input03.txt");
-    let instructions = parse_input(input)?.1;
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let instructions = parse_input(input).map_err(|e| e.to_owned())?.1;
 
     let part_a = instructions
         .iter()
@@ -56,8 +55,7 @@ fn main() -> Result<()> {
             _ => 0,
         })
         .sum::<usize>();
-    println!("Day 03, part A: {part_a}");
-    assert_eq!(part_a, 188116424);
+    run.part_a(part_a);
 
     let part_b = instructions
         .iter()
@@ -74,8 +72,7 @@ fn main() -> Result<()> {
             (sum, enabled)
         })
         .0;
-    println!("Day 03, part B: {part_b}");
-    assert_eq!(part_b, 104245808);
+    run.part_b(part_b);
 
     Ok(())
 }
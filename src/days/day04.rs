@@ -1,4 +1,5 @@
 use anyhow::{bail, Result};
+use aoc::runner::Run;
 use itertools::Itertools;
 
 struct Grid {
@@ -59,8 +60,7 @@ fn parse_input(input: &str) -> Result<Grid> {
     })
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input04.txt");
+pub fn solve(input: &str, run: &Run) -> Result<()> {
     let grid = parse_input(input)?;
 
     let cell_iter = (0..grid.width as isize).cartesian_product(0..grid.height as isize);
@@ -69,12 +69,10 @@ fn main() -> Result<()> {
         .clone()
         .map(|pos| grid.count_xmas_lines_at(pos))
         .sum::<usize>();
-    println!("Day 04, part A: {part_a}");
-    assert_eq!(part_a, 2521);
+    run.part_a(part_a);
 
     let part_b = cell_iter.filter(|pos| grid.has_xmas_x_at(*pos)).count();
-    println!("Day 04, part B: {part_b}");
-    assert_eq!(part_b, 1912);
+    run.part_b(part_b);
 
     Ok(())
 }
@@ -1,5 +1,6 @@
 use anyhow::Result;
-use aoc2024::util::parse_unsigned;
+use aoc::{parse::parse_unsigned, runner::Run};
+use itertools::Itertools;
 use nom::{
     character::complete::{char, newline},
     combinator::{all_consuming, opt},
@@ -34,7 +35,9 @@ fn parse_input(input: &str) -> IResult<&str, (Rules, Updates)> {
     ))(input)
 }
 
-fn fix_updates(updates: &mut Updates, rules: &Rules) {
+/// Moves each page that breaks a rule to just before the earliest page the
+/// rule says it must precede, until no rule is broken.
+fn fix_updates(updates: &mut Updates, rules: &Rules, run: &Run) {
     for update in updates {
         'outer: loop {
             let mut seen = HashMap::default();
@@ -47,6 +50,16 @@ fn fix_updates(updates: &mut Updates, rules: &Rules) {
                     .filter_map(|other| seen.get(other))
                     .min()
                 {
+                    if run.explaining() {
+                        run.explain(format!(
+                            "{}: rule {}|{} moves {} before {}",
+                            update.iter().join(","),
+                            update[i],
+                            update[*dest],
+                            update[i],
+                            update[*dest]
+                        ));
+                    }
                     let val = update.remove(i);
                     update.insert(*dest, val);
                     continue 'outer;
@@ -57,9 +70,8 @@ fn fix_updates(updates: &mut Updates, rules: &Rules) {
     }
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input05.txt");
-    let (rules, updates) = parse_input(input)?.1;
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let (rules, updates) = parse_input(input).map_err(|e| e.to_owned())?.1;
 
     let (good_updates, mut bad_updates): (Updates, Updates) =
         updates.into_iter().partition(|update| {
@@ -78,16 +90,14 @@ fn main() -> Result<()> {
         .iter()
         .map(|update| update[update.len() / 2])
         .sum::<usize>();
-    println!("Day 05, part A: {part_a}");
-    assert_eq!(part_a, 4766);
+    run.part_a(part_a);
 
-    fix_updates(&mut bad_updates, &rules);
+    fix_updates(&mut bad_updates, &rules, run);
     let part_b = bad_updates
         .iter()
         .map(|update| update[update.len() / 2])
         .sum::<usize>();
-    println!("Day 05, part B: {part_b}");
-    assert_eq!(part_b, 6257);
+    run.part_b(part_b);
 
     Ok(())
 }
@@ -0,0 +1,169 @@
+use anyhow::{bail, ensure, Context, Ok, Result};
+use aoc::{
+    budget::Budget,
+    gridset::{DirectionalGridSet, GridSet},
+    par::prelude::*,
+    render::{self, Image},
+    runner::Run,
+    sim::{self, Simulation},
+    sparse::SparseGrid,
+};
+use itertools::Itertools;
+
+#[derive(Clone)]
+struct Map {
+    width: isize,
+    height: isize,
+    obstacles: SparseGrid<()>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Guard {
+    pos: (isize, isize),
+    facing: (isize, isize),
+}
+
+fn parse_input(input: &str) -> Result<(Map, Guard)> {
+    let width = input.lines().next().map_or(0, |line| line.len() as isize);
+    let height = input.lines().count() as isize;
+    for line in input.trim_end().lines() {
+        ensure!(line.chars().count() as isize == width);
+    }
+    let mut guard = None;
+    let obstacles = SparseGrid::parse(input.trim_end(), |pos, c| {
+        Ok(match c {
+            '#' => Some(()),
+            '^' => {
+                guard = Some(Guard {
+                    pos,
+                    facing: (0, -1),
+                });
+                None
+            }
+            _ => None,
+        })
+    })?;
+
+    let map = Map {
+        width,
+        height,
+        obstacles,
+    };
+    let guard = guard.context("Couldn't find guard in input")?;
+    Ok((map, guard))
+}
+
+fn move_guard(map: &Map, guard: &mut Guard) {
+    let new_pos = (guard.pos.0 + guard.facing.0, guard.pos.1 + guard.facing.1);
+    if map.obstacles.contains(new_pos) {
+        guard.facing = (-guard.facing.1, guard.facing.0);
+    } else {
+        guard.pos = new_pos;
+    }
+}
+
+/// The guard walking the lab until they leave it.
+#[derive(Clone)]
+struct Patrol<'a> {
+    map: &'a Map,
+    guard: Guard,
+}
+
+impl Simulation for Patrol<'_> {
+    type State = Guard;
+
+    fn state(&self) -> &Guard {
+        &self.guard
+    }
+
+    fn step(&mut self) {
+        move_guard(self.map, &mut self.guard);
+    }
+
+    fn is_done(&self) -> bool {
+        !(0..self.map.width).contains(&self.guard.pos.0)
+            || !(0..self.map.height).contains(&self.guard.pos.1)
+    }
+}
+
+/// Whether the guard walks in a loop, found by remembering every position and
+/// facing they've had. Cheaper than `sim::find_cycle` for routes this short.
+fn is_loop(map: &Map, guard: Guard, budget: &Budget) -> Result<bool> {
+    let mut seen = DirectionalGridSet::new(map.width, map.height);
+    let mut patrol = Patrol { map, guard };
+    while !patrol.is_done() {
+        budget.step()?;
+        if !seen.insert(patrol.guard.pos, patrol.guard.facing) {
+            return Ok(true);
+        }
+        patrol.step();
+    }
+    Ok(false)
+}
+
+/// Every position the guard visits before leaving the lab.
+fn visited(map: &Map, guard: Guard, budget: &Budget) -> Result<GridSet> {
+    let mut visited = GridSet::new(map.width, map.height);
+    sim::trace(&mut Patrol { map, guard }, budget, |guard| {
+        visited.insert(guard.pos);
+    })?;
+    Ok(visited)
+}
+
+/// The guard's route until they leave the lab, as their position and the way
+/// they're facing at each step.
+pub fn trace(input: &str, budget: &Budget) -> Result<Vec<String>> {
+    let (map, guard) = parse_input(input)?;
+    let mut steps = Vec::new();
+    sim::trace(&mut Patrol { map: &map, guard }, budget, |guard| {
+        let facing = match guard.facing {
+            (0, -1) => '^',
+            (1, 0) => '>',
+            (0, 1) => 'v',
+            _ => '<',
+        };
+        steps.push(format!("{},{} {facing}", guard.pos.0, guard.pos.1));
+    })?;
+    Ok(steps)
+}
+
+/// The guard's route through the lab, starting from the red square.
+pub fn render(input: &str) -> Result<Image> {
+    let (map, guard) = parse_input(input)?;
+    let mut image = Image::new(map.width, map.height, 4, render::BLACK);
+    if !is_loop(&map, guard.clone(), &Budget::unlimited())? {
+        for pos in visited(&map, guard.clone(), &Budget::unlimited())?.iter() {
+            image.fill(pos, render::GREEN);
+        }
+    }
+    for (pos, _) in map.obstacles.iter() {
+        image.fill(pos, render::GREY);
+    }
+    image.fill(guard.pos, render::RED);
+    Ok(image)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let (map, guard) = parse_input(input)?;
+    let budget = run.budget();
+
+    if is_loop(&map, guard.clone(), budget)? {
+        bail!("Loop incorrectly detected in part A");
+    }
+    let visited = visited(&map, guard.clone(), budget)?.iter().collect_vec();
+    let part_a = visited.len();
+    run.part_a(part_a);
+
+    let part_b = visited
+        .into_maybe_par_iter()
+        .map(|pos| {
+            let mut map = map.clone();
+            map.obstacles.insert(pos, ());
+
+            Ok(is_loop(&map, guard.clone(), budget)? as usize)
+        })
+        .sum::<Result<usize>>()?;
+    run.part_b(part_b);
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anyhow::Result;
+use aoc::{budget::Budget, par::prelude::*, parse::parse_unsigned, runner::Run};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, newline},
+    combinator::{all_consuming, opt},
+    multi::separated_list1,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+fn parse_input(input: &str) -> IResult<&str, Vec<(usize, Vec<usize>)>> {
+    all_consuming(terminated(
+        separated_list1(
+            newline,
+            separated_pair(
+                parse_unsigned,
+                tag(": "),
+                separated_list1(char(' '), parse_unsigned),
+            ),
+        ),
+        opt(newline),
+    ))(input)
+}
+
+/// Works backwards from the result, undoing the last operator at each step.
+/// Only operators that can be undone exactly are explored, which prunes almost
+/// every branch, rather than building all 3^n forward results.
+fn is_solvable(
+    result: usize,
+    operands: &[usize],
+    allow_cat: bool,
+    budget: &Budget,
+) -> Result<bool> {
+    budget.step()?;
+    let (&last, rest) = match operands.split_last() {
+        Some((last, [])) => return Ok(result == *last),
+        Some(split) => split,
+        None => return Ok(false),
+    };
+
+    if result >= last && is_solvable(result - last, rest, allow_cat, budget)? {
+        return Ok(true);
+    }
+    if last == 0 {
+        if result == 0 {
+            return Ok(true);
+        }
+    } else if result.is_multiple_of(last) && is_solvable(result / last, rest, allow_cat, budget)? {
+        return Ok(true);
+    }
+    if allow_cat {
+        let shift = 10usize.pow(last.checked_ilog10().unwrap_or(0) + 1);
+        if result % shift == last && is_solvable(result / shift, rest, allow_cat, budget)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Like `is_solvable`, but returns the operators that solve the equation, in
+/// order. Only used for explaining, so it isn't budgeted.
+fn find_operators(result: usize, operands: &[usize], allow_cat: bool) -> Option<Vec<&'static str>> {
+    let (&last, rest) = match operands.split_last() {
+        Some((last, [])) => return (result == *last).then(Vec::new),
+        Some(split) => split,
+        None => return None,
+    };
+    let with = |mut operators: Vec<&'static str>, operator| {
+        operators.push(operator);
+        operators
+    };
+
+    if result >= last {
+        if let Some(operators) = find_operators(result - last, rest, allow_cat) {
+            return Some(with(operators, "+"));
+        }
+    }
+    if last == 0 {
+        if result == 0 {
+            return Some(with(vec!["*"; rest.len() - 1], "*"));
+        }
+    } else if result.is_multiple_of(last) {
+        if let Some(operators) = find_operators(result / last, rest, allow_cat) {
+            return Some(with(operators, "*"));
+        }
+    }
+    if allow_cat {
+        let shift = 10usize.pow(last.checked_ilog10().unwrap_or(0) + 1);
+        if result % shift == last {
+            if let Some(operators) = find_operators(result / shift, rest, allow_cat) {
+                return Some(with(operators, "||"));
+            }
+        }
+    }
+    None
+}
+
+fn explain_equations(equations: &[(usize, Vec<usize>)], allow_cat: bool, run: &Run) {
+    for (result, operands) in equations {
+        // Part B only lists the equations that need `||`, as a solution using
+        // it might be found even when `+` and `*` alone would do.
+        if allow_cat && find_operators(*result, operands, false).is_some() {
+            continue;
+        }
+        let Some(operators) = find_operators(*result, operands, allow_cat) else {
+            continue;
+        };
+        let expression = operands[1..].iter().zip(operators).fold(
+            operands[0].to_string(),
+            |expression, (operand, operator)| format!("{expression} {operator} {operand}"),
+        );
+        run.explain(format!("{result} = {expression}"));
+    }
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let equations = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let budget = run.budget();
+
+    let part_a = equations
+        .maybe_par_iter()
+        .map(|(result, operands)| {
+            Ok(if is_solvable(*result, operands, false, budget)? {
+                *result
+            } else {
+                0
+            })
+        })
+        .sum::<Result<usize>>()?;
+    run.part_a(part_a);
+    if run.explaining() {
+        run.explain("Part A:");
+        explain_equations(&equations, false, run);
+    }
+
+    let part_b = equations
+        .maybe_par_iter()
+        .map(|(result, operands)| {
+            Ok(if is_solvable(*result, operands, true, budget)? {
+                *result
+            } else {
+                0
+            })
+        })
+        .sum::<Result<usize>>()?;
+    run.part_b(part_b);
+    if run.explaining() {
+        run.explain("Part B also solves:");
+        explain_equations(&equations, true, run);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    fn solvable(equations: &[(usize, Vec<usize>)], allow_cat: bool) -> Vec<usize> {
+        equations
+            .iter()
+            .filter(|(result, operands)| {
+                is_solvable(*result, operands, allow_cat, &Budget::unlimited()).unwrap()
+            })
+            .map(|(result, _)| *result)
+            .collect()
+    }
+
+    #[test]
+    fn solves_example() {
+        let equations = parse_input(EXAMPLE).unwrap().1;
+        assert_eq!(solvable(&equations, false), [190, 3267, 292]);
+        assert_eq!(solvable(&equations, true), [190, 3267, 156, 7290, 192, 292]);
+    }
+
+    #[test]
+    fn concatenation_must_be_a_suffix() {
+        // 12 || 3 is 123, but 1 || 23 isn't reachable from 12 and 3.
+        let budget = Budget::unlimited();
+        assert!(is_solvable(123, &[12, 3], true, &budget).unwrap());
+        assert!(!is_solvable(123, &[12, 3], false, &budget).unwrap());
+        // 13 ends in 3, but 1 isn't 12, so undoing the concatenation fails.
+        assert!(!is_solvable(13, &[12, 3], true, &budget).unwrap());
+        // A zero still takes up a digit when concatenated.
+        assert!(is_solvable(100, &[10, 0], true, &budget).unwrap());
+        assert!(!is_solvable(101, &[10, 0], true, &budget).unwrap());
+    }
+
+    #[test]
+    fn explains_with_the_same_operators() {
+        assert_eq!(find_operators(156, &[15, 6], true), Some(vec!["||"]));
+        assert_eq!(
+            find_operators(292, &[11, 6, 16, 20], false),
+            Some(vec!["+", "*", "+"])
+        );
+        assert_eq!(find_operators(83, &[17, 5], true), None);
+    }
+}
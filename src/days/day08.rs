@@ -1,4 +1,5 @@
 use anyhow::Result;
+use aoc::runner::Run;
 use itertools::{Either, Itertools};
 use std::collections::{HashMap, HashSet};
 
@@ -67,8 +68,7 @@ fn get_antinodes(
         })
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input08.txt");
+pub fn solve(input: &str, run: &Run) -> Result<()> {
     let map = parse_input(input);
 
     let part_a = map
@@ -77,8 +77,7 @@ fn main() -> Result<()> {
         .flat_map(|antennae| get_antinodes(antennae, map.width, map.height, false))
         .unique()
         .count();
-    println!("Day 08, part A: {}", part_a);
-    assert_eq!(part_a, 222);
+    run.part_a(part_a);
 
     let part_b = map
         .antennae
@@ -86,8 +85,7 @@ fn main() -> Result<()> {
         .flat_map(|antennae| get_antinodes(antennae, map.width, map.height, true))
         .unique()
         .count();
-    println!("Day 08, part B: {}", part_b);
-    assert_eq!(part_b, 884);
+    run.part_b(part_b);
 
     Ok(())
 }
@@ -1,5 +1,5 @@
 use anyhow::{Ok, Result};
-use aoc2024::util::parse_unsigned;
+use aoc::{budget::Budget, parse::parse_unsigned, runner::Run};
 use itertools::Itertools;
 use nom::{
     bytes::complete::take,
@@ -38,7 +38,7 @@ fn defrag_a(mut disk: Disk) -> Disk {
     disk
 }
 
-fn defrag_b(mut disk: Disk) -> Disk {
+fn defrag_b(mut disk: Disk, budget: &Budget) -> Result<Disk> {
     let mut end = disk.len() - 1;
     let mut seen: HashSet<usize> = HashSet::default();
     while end > 0 {
@@ -46,6 +46,7 @@ fn defrag_b(mut disk: Disk) -> Disk {
             None => end -= 1,
             Some(id) if seen.contains(&id) => end -= 1,
             Some(id) => {
+                budget.step()?;
                 seen.insert(id);
 
                 let mut file_start = end;
@@ -64,7 +65,7 @@ fn defrag_b(mut disk: Disk) -> Disk {
         }
     }
 
-    disk
+    Ok(disk)
 }
 
 fn checksum(disk: &[Option<usize>]) -> usize {
@@ -74,9 +75,8 @@ fn checksum(disk: &[Option<usize>]) -> usize {
         .sum::<usize>()
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input09.txt");
-    let disk_map = parse_input(input)?.1;
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let disk_map = parse_input(input).map_err(|e| e.to_owned())?.1;
 
     let disk = disk_map
         .chunks(2)
@@ -84,19 +84,15 @@ fn main() -> Result<()> {
         .flat_map(|(id, chunk)| {
             let file_size = chunk[0];
             let free_size = *chunk.get(1).unwrap_or(&0);
-            std::iter::repeat(Some(id))
-                .take(file_size)
-                .chain(std::iter::repeat(None).take(free_size))
+            std::iter::repeat_n(Some(id), file_size).chain(std::iter::repeat_n(None, free_size))
         })
         .collect_vec();
 
     let part_a = checksum(&defrag_a(disk.clone()));
-    println!("Day 09, part A: {part_a}");
-    assert_eq!(part_a, 6201130364722);
+    run.part_a(part_a);
 
-    let part_b = checksum(&defrag_b(disk));
-    println!("Day 09, part B: {part_b}");
-    assert_eq!(part_b, 6221662795602);
+    let part_b = checksum(&defrag_b(disk, run.budget())?);
+    run.part_b(part_b);
 
     Ok(())
 }
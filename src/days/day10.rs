@@ -0,0 +1,120 @@
+use anyhow::{Context, Result};
+use aoc::{
+    budget::Budget,
+    grid::{Grid, Pos},
+    par::prelude::*,
+    runner::Run,
+};
+use itertools::Itertools;
+use rustc_hash::FxHashMap as HashMap;
+
+struct Map {
+    elevations: Grid<u32>,
+}
+
+impl Map {
+    fn get(&self, pos: Pos) -> Option<u32> {
+        self.elevations.get(pos).copied()
+    }
+
+    fn trailheads(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.elevations
+            .iter()
+            .filter_map(|(pos, &elevation)| (elevation == 0).then_some(pos))
+    }
+
+    /// Returns the number of distinct summits reachable from `head`, and the
+    /// number of distinct trails leading to them. Rather than enumerating
+    /// every trail, this walks up one elevation at a time keeping a count of
+    /// how many trails reach each position.
+    fn count_trails(&self, head: Pos, budget: &Budget) -> Result<(usize, usize)> {
+        assert!(self.get(head) == Some(0));
+        let mut trails: HashMap<Pos, usize> = HashMap::default();
+        trails.insert(head, 1);
+        for elevation in 1..=9 {
+            let mut next_trails: HashMap<Pos, usize> = HashMap::default();
+            for (pos, count) in trails {
+                budget.step()?;
+                for next_pos in self.elevations.neighbours(pos) {
+                    if self.get(next_pos) == Some(elevation) {
+                        *next_trails.entry(next_pos).or_default() += count;
+                    }
+                }
+            }
+            budget.check_items("count_trails", next_trails.len())?;
+            trails = next_trails;
+        }
+        Ok((trails.len(), trails.values().sum()))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Map> {
+    let elevations = Grid::parse(input, |_, c| {
+        c.to_digit(10)
+            .with_context(|| format!("Invalid input: {}", c))
+    })?;
+    Ok(Map { elevations })
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let map = parse_input(input)?;
+    let budget = run.budget();
+
+    let trailheads = map
+        .trailheads()
+        .collect_vec()
+        .into_maybe_par_iter()
+        .map(|trailhead| map.count_trails(trailhead, budget))
+        .collect::<Result<Vec<_>>>()?;
+
+    let part_a = trailheads.iter().map(|(ends, _)| ends).sum::<usize>();
+    run.part_a(part_a);
+
+    let part_b = trailheads.iter().map(|(_, trails)| trails).sum::<usize>();
+    run.part_b(part_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
+
+    fn count(input: &str) -> Vec<(usize, usize)> {
+        let map = parse_input(input).unwrap();
+        map.trailheads()
+            .map(|head| map.count_trails(head, &Budget::unlimited()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn counts_example_trails() {
+        let trails = count(EXAMPLE);
+        assert_eq!(trails.len(), 9);
+        assert_eq!(trails.iter().map(|(ends, _)| ends).sum::<usize>(), 36);
+        assert_eq!(trails.iter().map(|(_, trails)| trails).sum::<usize>(), 81);
+    }
+
+    #[test]
+    fn counts_trails_through_a_fork() {
+        // Every right-or-down path is a trail, and they all meet at one summit.
+        let trails = count("0123\n1234\n2345\n3456\n4567\n5678\n6789\n");
+        assert_eq!(trails, [(1, 84)]);
+    }
+
+    #[test]
+    fn map_without_trailheads() {
+        assert!(count("123\n456\n789\n").is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use aoc2024::util::parse_unsigned;
+use aoc::{budget::Budget, parse::parse_unsigned, runner::Run};
 use nom::{
     character::complete::{char, multispace0},
     combinator::all_consuming,
@@ -35,24 +35,24 @@ fn blink(stones: HashMap<u64, u64>) -> HashMap<u64, u64> {
         })
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input11.txt");
-    let stones = parse_input(input)?.1;
+fn blinks(stones: HashMap<u64, u64>, n: usize, budget: &Budget) -> Result<HashMap<u64, u64>> {
+    (0..n).try_fold(stones, |stones, _| {
+        budget.step()?;
+        Ok(blink(stones))
+    })
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let stones = parse_input(input).map_err(|e| e.to_owned())?.1;
     let stones: HashMap<u64, u64> = stones.into_iter().map(|stone| (stone, 1)).collect();
 
-    let part_a = (0..25)
-        .fold(stones.clone(), |stones, _| blink(stones))
+    let part_a = blinks(stones.clone(), 25, run.budget())?
         .values()
         .sum::<u64>();
-    println!("Day 11, part A: {}", part_a);
-    assert_eq!(part_a, 203953);
+    run.part_a(part_a);
 
-    let part_b = (0..75)
-        .fold(stones, |stones, _| blink(stones))
-        .values()
-        .sum::<u64>();
-    println!("Day 11, part B: {}", part_b);
-    assert_eq!(part_b, 242090118578155);
+    let part_b = blinks(stones, 75, run.budget())?.values().sum::<u64>();
+    run.part_b(part_b);
 
     Ok(())
 }
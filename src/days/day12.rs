@@ -0,0 +1,109 @@
+use anyhow::Result;
+use aoc::{
+    components::{self, Connectivity},
+    grid::{Grid, Pos},
+    gridset::GridSet,
+    par::prelude::*,
+    render::{self, Image},
+    runner::Run,
+};
+
+type Map = Grid<char>;
+
+fn parse_input(input: &str) -> Result<Map> {
+    Grid::parse(input, |_, c| Ok(c))
+}
+
+fn find_regions(map: &Map) -> Vec<Vec<Pos>> {
+    let components = components::label(map, Connectivity::Four, |a, b| a == b);
+    let mut regions = components
+        .sizes
+        .iter()
+        .map(|&size| Vec::with_capacity(size))
+        .collect::<Vec<_>>();
+    for (pos, &label) in components.labels.iter() {
+        regions[label].push(pos);
+    }
+    regions
+}
+
+/// Sums `f` over every region in parallel, passing it the region's positions
+/// along with a set holding just those positions. Each worker reuses one set
+/// from region to region rather than allocating one per region.
+fn sum_regions(
+    map: &Map,
+    regions: &[Vec<Pos>],
+    f: impl Fn(&[Pos], &GridSet) -> usize + Sync,
+) -> usize {
+    regions
+        .maybe_par_iter()
+        .map_init(
+            || GridSet::for_grid(map),
+            |cells, region| {
+                cells.extend(region.iter().copied());
+                let value = f(region, cells);
+                for &pos in region {
+                    cells.remove(pos);
+                }
+                value
+            },
+        )
+        .sum()
+}
+
+fn perimeter(region: &[Pos], cells: &GridSet) -> usize {
+    region
+        .iter()
+        .map(|pos| {
+            4 - [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .filter(|step| cells.contains((pos.0 + step.0, pos.1 + step.1)))
+                .count()
+        })
+        .sum::<usize>()
+}
+
+fn edges(region: &[Pos], cells: &GridSet) -> usize {
+    region
+        .iter()
+        .map(|pos| {
+            [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .filter(|step| {
+                    !cells.contains((pos.0 + step.0, pos.1 + step.1))
+                        && (!cells.contains((pos.0 + step.1, pos.1 + step.0))
+                            || cells.contains((pos.0 + step.0 + step.1, pos.1 + step.0 + step.1)))
+                })
+                .count()
+        })
+        .sum::<usize>()
+}
+
+/// Each region in its own colour.
+pub fn render(input: &str) -> Result<Image> {
+    let map = parse_input(input)?;
+    let mut image = Image::new(map.width(), map.height(), 4, render::BLACK);
+    for (i, region) in find_regions(&map).iter().enumerate() {
+        for &pos in region {
+            image.fill(pos, render::palette(i));
+        }
+    }
+    Ok(image)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let map = parse_input(input)?;
+    let regions = find_regions(&map);
+
+    let part_a = sum_regions(&map, &regions, |region, cells| {
+        region.len() * perimeter(region, cells)
+    });
+    run.part_a(part_a);
+
+    let part_b = sum_regions(&map, &regions, |region, cells| {
+        region.len() * edges(region, cells)
+    });
+    run.part_b(part_b);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use aoc::{
+    num::{determinant, exact_div},
+    parse::parse_unsigned,
+    runner::{Run, Violation},
+};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::newline,
+    combinator::{all_consuming, map, opt},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair, terminated, tuple},
+    IResult,
+};
+
+struct Game {
+    button_a: (i128, i128),
+    button_b: (i128, i128),
+    prize: (i128, i128),
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Game>> {
+    all_consuming(terminated(
+        separated_list1(
+            newline,
+            map(
+                tuple((
+                    delimited(
+                        tag("Button A: X+"),
+                        separated_pair(parse_unsigned, tag(", Y+"), parse_unsigned),
+                        opt(newline),
+                    ),
+                    delimited(
+                        tag("Button B: X+"),
+                        separated_pair(parse_unsigned, tag(", Y+"), parse_unsigned),
+                        opt(newline),
+                    ),
+                    delimited(
+                        tag("Prize: X="),
+                        separated_pair(parse_unsigned, tag(", Y="), parse_unsigned),
+                        opt(newline),
+                    ),
+                )),
+                |(button_a, button_b, prize)| Game {
+                    button_a,
+                    button_b,
+                    prize,
+                },
+            ),
+        ),
+        opt(newline),
+    ))(input)
+}
+
+fn buttons_determinant(game: &Game) -> Result<i128> {
+    determinant([
+        [game.button_a.0, game.button_b.0],
+        [game.button_a.1, game.button_b.1],
+    ])
+}
+
+/// The tokens needed to win the prize, if it can be won with a whole number of
+/// presses of each button.
+fn solve_game(game: &Game) -> Result<Option<i128>> {
+    let det = buttons_determinant(game)?;
+    let det_a = determinant([
+        [game.prize.0, game.prize.1],
+        [game.button_b.0, game.button_b.1],
+    ])?;
+    let det_b = determinant([
+        [game.button_a.0, game.button_a.1],
+        [game.prize.0, game.prize.1],
+    ])?;
+    Ok(exact_div(det_a, det)
+        .zip(exact_div(det_b, det))
+        .filter(|&(a, b)| a >= 0 && b >= 0)
+        .map(|(a, b)| 3 * a + b))
+}
+
+/// Each machine is solved as a pair of simultaneous equations, which only has
+/// a single solution when the buttons move the claw in different directions.
+pub fn validate(input: &str) -> Result<Vec<Violation>> {
+    let games = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let mut violations = Vec::new();
+    for (i, game) in games.iter().enumerate() {
+        if buttons_determinant(game)? == 0 {
+            violations.push(Violation::new(format!(
+                "Machine {}: the buttons' determinant is zero",
+                i + 1
+            )));
+        }
+    }
+    Ok(violations)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let games = parse_input(input).map_err(|e| e.to_owned())?.1;
+
+    let part_a = games
+        .iter()
+        .map(solve_game)
+        .flatten_ok()
+        .sum::<Result<i128>>()?;
+    run.part_a(part_a);
+
+    let games = games
+        .into_iter()
+        .map(|game| Game {
+            button_a: game.button_a,
+            button_b: game.button_b,
+            prize: (game.prize.0 + 10000000000000, game.prize.1 + 10000000000000),
+        })
+        .collect_vec();
+
+    let part_b = games
+        .iter()
+        .map(solve_game)
+        .flatten_ok()
+        .sum::<Result<i128>>()?;
+    run.part_b(part_b);
+
+    Ok(())
+}
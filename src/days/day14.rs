@@ -0,0 +1,213 @@
+use anyhow::{Context, Ok, Result};
+use aoc::{
+    budget::Budget,
+    grid::{Grid, Torus},
+    num::crt,
+    parse::parse_signed,
+    render::{self, Image},
+    runner::{Part, Run, Violation},
+    sim::{self, Simulation},
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, newline},
+    combinator::{all_consuming, map, opt},
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair, terminated},
+    IResult,
+};
+
+#[derive(Clone, PartialEq, Eq)]
+struct Robot {
+    pos: (isize, isize),
+    velocity: (isize, isize),
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Robot>> {
+    all_consuming(terminated(
+        separated_list1(
+            newline,
+            map(
+                pair(
+                    preceded(
+                        tag("p="),
+                        separated_pair(parse_signed, char(','), parse_signed),
+                    ),
+                    preceded(
+                        tag(" v="),
+                        separated_pair(parse_signed, char(','), parse_signed),
+                    ),
+                ),
+                |(pos, velocity)| Robot { pos, velocity },
+            ),
+        ),
+        opt(newline),
+    ))(input)
+}
+
+const WIDTH: isize = 101;
+const HEIGHT: isize = 103;
+
+/// After how many seconds the robots first bunch up into a vertical band,
+/// which recurs every `WIDTH` seconds, and into a horizontal band, which
+/// recurs every `HEIGHT` seconds.
+const VERTICAL_OFFSET: isize = 11;
+const HORIZONTAL_OFFSET: isize = 65;
+
+/// The robots moving around the bathroom, one second per step. `floor` counts
+/// the robots on each tile.
+#[derive(Clone)]
+struct Lobby {
+    robots: Vec<Robot>,
+    floor: Torus<usize>,
+}
+
+impl Lobby {
+    fn new(robots: Vec<Robot>) -> Self {
+        let mut floor = Torus::new(Grid::filled(WIDTH, HEIGHT, 0)).unwrap();
+        for robot in &robots {
+            *floor.get_mut(robot.pos) += 1;
+        }
+        Self { robots, floor }
+    }
+}
+
+impl Simulation for Lobby {
+    type State = Vec<Robot>;
+
+    fn state(&self) -> &Vec<Robot> {
+        &self.robots
+    }
+
+    fn step(&mut self) {
+        for robot in &mut self.robots {
+            *self.floor.get_mut(robot.pos) -= 1;
+            robot.pos = self.floor.wrap((
+                robot.pos.0 + robot.velocity.0,
+                robot.pos.1 + robot.velocity.1,
+            ));
+            *self.floor.get_mut(robot.pos) += 1;
+        }
+    }
+}
+
+fn count_quadrants(robots: &[Robot]) -> [usize; 4] {
+    robots
+        .iter()
+        .fold([0, 0, 0, 0], |[tl, tr, bl, br], robot| match robot.pos {
+            (x, y) if x < WIDTH / 2 && y < HEIGHT / 2 => [tl + 1, tr, bl, br],
+            (x, y) if x > WIDTH / 2 && y < HEIGHT / 2 => [tl, tr + 1, bl, br],
+            (x, y) if x < WIDTH / 2 && y > HEIGHT / 2 => [tl, tr, bl + 1, br],
+            (x, y) if x > WIDTH / 2 && y > HEIGHT / 2 => [tl, tr, bl, br + 1],
+            _ => [tl, tr, bl, br],
+        })
+}
+
+fn draw_robots(lobby: &Lobby, scale: usize) -> Image {
+    render::draw_grid(lobby.floor.grid(), scale, |&robots| {
+        (robots > 0).then_some(render::GREEN)
+    })
+}
+
+/// The number of seconds, below `period`, after which the robots' positions
+/// along one axis are most tightly bunched.
+fn tightest(robots: &[Robot], period: isize, axis: impl Fn((isize, isize)) -> isize) -> isize {
+    (0..period)
+        .min_by_key(|&t| {
+            let values = robots
+                .iter()
+                .map(|robot| (axis(robot.pos) + axis(robot.velocity) * t).rem_euclid(period))
+                .collect::<Vec<_>>();
+            let mean = values.iter().sum::<isize>() / values.len().max(1) as isize;
+            values.iter().map(|v| (v - mean).pow(2)).sum::<isize>()
+        })
+        .unwrap()
+}
+
+/// How many seconds pass before the robots' vertical and horizontal bands
+/// line up into the tree.
+fn tree_time() -> Result<usize> {
+    let (time, _) = crt(&[
+        (VERTICAL_OFFSET as i128, WIDTH as i128),
+        (HORIZONTAL_OFFSET as i128, HEIGHT as i128),
+    ])?
+    .context("The vertical and horizontal bands never line up")?;
+    Ok(time as usize)
+}
+
+/// The robots once they've arranged themselves into the tree.
+pub fn render(input: &str) -> Result<Image> {
+    let robots = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let mut lobby = Lobby::new(robots);
+    sim::run(&mut lobby, tree_time()?, &Budget::unlimited())?;
+    Ok(draw_robots(&lobby, 4))
+}
+
+/// Part B only finds the tree if the robots' bands line up with the offsets
+/// read off the images, and both parts assume the robots start on the map.
+pub fn validate(input: &str) -> Result<Vec<Violation>> {
+    let robots = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let mut violations = Vec::new();
+    let outside = robots
+        .iter()
+        .filter(|robot| !(0..WIDTH).contains(&robot.pos.0) || !(0..HEIGHT).contains(&robot.pos.1))
+        .count();
+    if outside > 0 {
+        violations.push(Violation::new(format!(
+            "{outside} robots start outside the {WIDTH}x{HEIGHT} map"
+        )));
+    }
+    let vertical = tightest(&robots, WIDTH, |pos| pos.0);
+    if vertical != VERTICAL_OFFSET {
+        violations.push(Violation::of_part(
+            Part::B,
+            format!("The robots bunch into a vertical band after {vertical} seconds, not {VERTICAL_OFFSET}"),
+        ));
+    }
+    let horizontal = tightest(&robots, HEIGHT, |pos| pos.1);
+    if horizontal != HORIZONTAL_OFFSET {
+        violations.push(Violation::of_part(
+            Part::B,
+            format!("The robots bunch into a horizontal band after {horizontal} seconds, not {HORIZONTAL_OFFSET}"),
+        ));
+    }
+    Ok(violations)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let robots = parse_input(input).map_err(|e| e.to_owned())?.1;
+
+    let mut lobby = Lobby::new(robots.clone());
+    sim::run(&mut lobby, 100, run.budget())?;
+    let part_a = count_quadrants(&lobby.robots)
+        .into_iter()
+        .product::<usize>();
+    run.part_a(part_a);
+
+    // Save an image of the first 10000 robot arrangements. Needs `--features viz`.
+    // let mut lobby = Lobby::new(robots);
+    // for i in 0..10000 {
+    //     draw_robots(&lobby, 1)
+    //         .save_png(format!("day14_{:05}.png", i))
+    //         .unwrap();
+    //     lobby.step();
+    // }
+
+    // Looking at the images, we can see that usually the robots are randomly spread out.
+    // However, every 101 frames they mostly appear in a vertical line, first occuring at
+    // frame 12. And every 103 frames they mostly appear in a horizontal line, first
+    // occuring at frame 65. The tree will occur when these two patterns overlap.
+    let part_b = tree_time()?;
+    run.part_b(part_b);
+
+    if run.explaining() {
+        let cycle = sim::find_cycle(&Lobby::new(robots), run.budget())?
+            .context("The robots stopped moving")?;
+        run.explain(format!(
+            "The robots are back where they started every {} seconds, so the tree appears after {part_b} seconds and every {} seconds after that",
+            cycle.period, cycle.period
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,399 @@
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use aoc::{
+    budget::Budget,
+    render::{self, Image},
+    runner::{Part, Run, Session, Violation},
+    sim::{self, Simulation},
+};
+use itertools::Itertools;
+use std::path::Path;
+
+type Pos = (isize, isize);
+
+#[derive(Copy, Clone, PartialEq)]
+enum Tile {
+    Empty,
+    Wall,
+    SmallBox,
+    BigBoxLeft,
+    BigBoxRight,
+}
+
+#[derive(Clone)]
+struct Map {
+    width: isize,
+    height: isize,
+    tiles: Vec<Tile>,
+}
+
+impl Map {
+    fn get(&self, pos: Pos) -> &Tile {
+        &self.tiles[(pos.1 * self.width + pos.0) as usize]
+    }
+
+    fn get_mut(&mut self, pos: Pos) -> &mut Tile {
+        &mut self.tiles[(pos.1 * self.width + pos.0) as usize]
+    }
+
+    fn to_text(&self, robot: Pos) -> String {
+        let mut text = String::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                text.push(match (robot == (x, y), self.get((x, y))) {
+                    (true, _) => '@',
+                    (_, Tile::Empty) => '.',
+                    (_, Tile::Wall) => '#',
+                    (_, Tile::SmallBox) => 'O',
+                    (_, Tile::BigBoxLeft) => '[',
+                    (_, Tile::BigBoxRight) => ']',
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    fn score(&self) -> isize {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| matches!(tile, Tile::SmallBox | Tile::BigBoxLeft))
+            .map(|(i, _)| 100 * (i as isize / self.width) + i as isize % self.width)
+            .sum()
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Pos, Map, Vec<Pos>)> {
+    let (map, directions) = input
+        .split_once("\n\n")
+        .context("No separate map and directions sections")?;
+
+    let width = map.lines().next().context("Empty input")?.len() as isize;
+    let height = map.lines().count() as isize;
+    let (robot, tiles) = map
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(move |(x, c)| ((x as isize, y as isize), c))
+        })
+        .try_fold((None, Vec::new()), |(mut robot, mut tiles), (pos, c)| {
+            match c {
+                '#' => {
+                    tiles.push(Tile::Wall);
+                }
+                'O' => {
+                    tiles.push(Tile::SmallBox);
+                }
+                '@' => {
+                    ensure!(robot.is_none(), "Multiple robots found");
+                    tiles.push(Tile::Empty);
+                    robot = Some(pos);
+                }
+                '.' => {
+                    tiles.push(Tile::Empty);
+                }
+                _ => bail!("Invalid character in map: {}", c),
+            }
+            Ok((robot, tiles))
+        })?;
+    let robot = robot.context("Robot position not found")?;
+
+    let directions: Vec<Pos> = directions
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '<' => Ok((-1, 0)),
+            '>' => Ok((1, 0)),
+            '^' => Ok((0, -1)),
+            'v' => Ok((0, 1)),
+            _ => Err(anyhow!("Invalid direction: {}", c)),
+        })
+        .try_collect()?;
+
+    Ok((
+        robot,
+        Map {
+            width,
+            height,
+            tiles,
+        },
+        directions,
+    ))
+}
+
+fn resize_map(robot: Pos, mut map: Map) -> (Pos, Map) {
+    let tiles = map
+        .tiles
+        .drain(..)
+        .flat_map(|tile| match tile {
+            Tile::Empty => [Tile::Empty, Tile::Empty],
+            Tile::Wall => [Tile::Wall, Tile::Wall],
+            Tile::SmallBox => [Tile::BigBoxLeft, Tile::BigBoxRight],
+            Tile::BigBoxLeft | Tile::BigBoxRight => unimplemented!(),
+        })
+        .collect();
+
+    (
+        (robot.0 * 2, robot.1),
+        Map {
+            width: map.width * 2,
+            height: map.height,
+            tiles,
+        },
+    )
+}
+
+fn can_move(map: &Map, pos: Pos, direction: Pos) -> Option<Vec<Pos>> {
+    let mut boxes = Vec::new();
+    let mut open = Vec::new();
+    open.push(pos);
+
+    while let Some(next) = open.pop() {
+        let tile = *map.get(next);
+        match tile {
+            Tile::Wall => return None,
+            Tile::Empty => {}
+            Tile::SmallBox | Tile::BigBoxLeft | Tile::BigBoxRight => {
+                if !boxes.contains(&next) {
+                    boxes.push(next);
+                    match direction {
+                        (-1, 0) | (1, 0) => {
+                            open.push((next.0 + direction.0, next.1));
+                        }
+                        (0, -1) | (0, 1) => {
+                            open.push((next.0, next.1 + direction.1));
+                            if matches!(tile, Tile::BigBoxLeft) {
+                                open.push((next.0 + 1, next.1));
+                            } else if matches!(tile, Tile::BigBoxRight) {
+                                open.push((next.0 - 1, next.1));
+                            }
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+
+    Some(boxes)
+}
+
+fn move_robot(robot: &mut Pos, map: &mut Map, direction: Pos) {
+    let to_move = can_move(
+        map,
+        (robot.0 + direction.0, robot.1 + direction.1),
+        direction,
+    );
+
+    if let Some(boxes) = to_move {
+        *robot = (robot.0 + direction.0, robot.1 + direction.1);
+        let old_map = map.clone();
+        for b in &boxes {
+            if !boxes.contains(&(b.0 - direction.0, b.1 - direction.1)) {
+                *map.get_mut(*b) = Tile::Empty;
+            }
+            *map.get_mut((b.0 + direction.0, b.1 + direction.1)) = *old_map.get(*b);
+        }
+    }
+}
+
+/// The warehouse part way through the robot's moves. In the REPL, `set wide`
+/// switches between the part A and part B warehouses, starting again from the
+/// first move.
+#[derive(Clone)]
+struct Warehouse {
+    start: (Pos, Map),
+    directions: Vec<Pos>,
+    wide: bool,
+    state: (Pos, Map),
+    moves: usize,
+}
+
+impl Warehouse {
+    fn new(start: (Pos, Map), directions: Vec<Pos>, wide: bool) -> Self {
+        let mut warehouse = Self {
+            state: start.clone(),
+            start,
+            directions,
+            wide,
+            moves: 0,
+        };
+        warehouse.reset();
+        warehouse
+    }
+
+    fn reset(&mut self) {
+        let (robot, map) = self.start.clone();
+        self.state = if self.wide {
+            resize_map(robot, map)
+        } else {
+            (robot, map)
+        };
+        self.moves = 0;
+    }
+}
+
+impl Simulation for Warehouse {
+    type State = (Pos, Map);
+
+    fn state(&self) -> &(Pos, Map) {
+        &self.state
+    }
+
+    fn step(&mut self) {
+        let (robot, map) = &mut self.state;
+        move_robot(robot, map, self.directions[self.moves]);
+        self.moves += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.moves == self.directions.len()
+    }
+}
+
+pub fn repl(input: &str) -> Result<Box<dyn Session>> {
+    let (robot, map, directions) = parse_input(input)?;
+    Ok(Box::new(Warehouse::new((robot, map), directions, false)))
+}
+
+impl Session for Warehouse {
+    fn show(&self) -> String {
+        let (robot, map) = &self.state;
+        format!(
+            "{}Move {} of {}, GPS sum {}\n",
+            map.to_text(*robot),
+            self.moves,
+            self.directions.len(),
+            map.score()
+        )
+    }
+
+    fn step(&mut self, n: usize) -> Result<()> {
+        let steps = sim::run(self, n, &Budget::unlimited())?;
+        ensure!(steps == n, "The robot has no moves left");
+        Ok(())
+    }
+
+    /// Finishes the remaining moves in the part's warehouse, starting from the
+    /// current state if it is already that warehouse.
+    fn part(&self, part: Part, budget: &Budget) -> Result<String> {
+        let wide = part == Part::B;
+        let mut warehouse = if wide == self.wide {
+            self.clone()
+        } else {
+            Warehouse::new(self.start.clone(), self.directions.clone(), wide)
+        };
+        sim::run_until_done(&mut warehouse, budget)?;
+        Ok(warehouse.state.1.score().to_string())
+    }
+
+    fn set(&mut self, param: &str, value: &str) -> Result<()> {
+        match param {
+            "wide" => {
+                self.wide = value
+                    .parse()
+                    .with_context(|| format!("Invalid value for wide: {value}"))?;
+                self.reset();
+            }
+            _ => bail!("No such parameter: {param}. Try wide"),
+        }
+        Ok(())
+    }
+
+    fn render(&self, path: &Path) -> Result<()> {
+        draw_warehouse(self.state.0, &self.state.1).save_png(path)
+    }
+}
+
+fn draw_warehouse(robot: Pos, map: &Map) -> Image {
+    let mut image = Image::new(map.width, map.height, 8, render::BLACK);
+    for y in 0..map.height {
+        for x in 0..map.width {
+            let colour = match map.get((x, y)) {
+                Tile::Empty => continue,
+                Tile::Wall => render::GREY,
+                Tile::SmallBox | Tile::BigBoxLeft | Tile::BigBoxRight => render::BROWN,
+            };
+            image.fill((x, y), colour);
+        }
+    }
+    image.fill(robot, render::GREEN);
+    image
+}
+
+/// Where the robot is before each of its moves around the part A warehouse.
+pub fn trace(input: &str, budget: &Budget) -> Result<Vec<String>> {
+    let (robot, map, directions) = parse_input(input)?;
+    let mut steps = Vec::new();
+    let mut warehouse = Warehouse::new((robot, map), directions, false);
+    sim::trace(&mut warehouse, budget, |(robot, _)| {
+        steps.push(format!("{},{}", robot.0, robot.1));
+    })?;
+    Ok(steps)
+}
+
+/// The wide warehouse once the robot has finished moving.
+pub fn render(input: &str) -> Result<Image> {
+    let (robot, map, directions) = parse_input(input)?;
+    let mut warehouse = Warehouse::new((robot, map), directions, true);
+    sim::run_until_done(&mut warehouse, &Budget::unlimited())?;
+    let (robot, map) = warehouse.state;
+    Ok(draw_warehouse(robot, &map))
+}
+
+/// Robots and boxes are moved without bounds checks, relying on the map being
+/// rectangular and walled in on every side.
+pub fn validate(input: &str) -> Result<Vec<Violation>> {
+    parse_input(input)?;
+    let lines = input
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .lines()
+        .collect_vec();
+    let width = lines.first().map_or(0, |line| line.len());
+    let mut violations = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.len() != width)
+        .map(|(y, line)| Violation::new(format!("Row {y} is {} wide, not {width}", line.len())))
+        .collect_vec();
+    let last = lines.len().saturating_sub(1);
+    let gaps = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(move |&(x, c)| {
+                    (y == 0 || y == last || x == 0 || x + 1 == line.len()) && c != '#'
+                })
+                .map(move |(x, _)| (x, y))
+        })
+        .collect_vec();
+    if let Some(first) = gaps.first() {
+        violations.push(Violation::new(format!(
+            "{} gaps in the wall around the map, first at {first:?}",
+            gaps.len()
+        )));
+    }
+    Ok(violations)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let (robot, map, directions) = parse_input(input)?;
+
+    let mut warehouse = Warehouse::new((robot, map.clone()), directions.clone(), false);
+    sim::run_until_done(&mut warehouse, run.budget())?;
+    let part_a = warehouse.state.1.score();
+    run.part_a(part_a);
+
+    let mut warehouse = Warehouse::new((robot, map), directions, true);
+    sim::run_until_done(&mut warehouse, run.budget())?;
+    let part_b = warehouse.state.1.score();
+    run.part_b(part_b);
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anyhow::{bail, ensure, Context, Result};
+use aoc::{budget::Budget, runner::Run};
 use itertools::Itertools;
 use rustc_hash::FxHashMap as HashMap;
 use std::{cmp::Ordering, collections::BinaryHeap};
@@ -87,7 +88,7 @@ impl PartialOrd for State {
     }
 }
 
-fn solve(map: &Map) -> Result<(u32, usize)> {
+fn solve_maze(map: &Map, budget: &Budget) -> Result<(u32, usize)> {
     let mut finished_paths = Vec::new();
     let mut finished_cost = None;
     let mut costs = HashMap::default();
@@ -99,11 +100,12 @@ fn solve(map: &Map) -> Result<(u32, usize)> {
     });
 
     while let Some(state) = open.pop() {
+        budget.step()?;
         let pos = *state.path.last().unwrap();
 
         if costs
             .get(&(pos, state.dir))
-            .map_or(false, |&cost| state.cost > cost)
+            .is_some_and(|&cost| state.cost > cost)
         {
             continue;
         }
@@ -150,14 +152,11 @@ fn solve(map: &Map) -> Result<(u32, usize)> {
     ))
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input16.txt");
+pub fn solve(input: &str, run: &Run) -> Result<()> {
     let map = parse_input(input)?;
 
-    let (part_a, part_b) = solve(&map)?;
-    println!("Day 16, part A: {part_a:?}");
-    assert_eq!(part_a, 65436);
-    println!("Day 16, part B: {part_b:?}");
-    assert_eq!(part_b, 489);
+    let (part_a, part_b) = solve_maze(&map, run.budget())?;
+    run.part_a(part_a);
+    run.part_b(part_b);
     Ok(())
 }
@@ -0,0 +1,279 @@
+use anyhow::{bail, Context, Result};
+use aoc::{
+    budget::{Budget, BudgetExceeded},
+    parse::parse_unsigned,
+    runner::{Part, Run, Session, Violation},
+};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, newline},
+    combinator::{all_consuming, opt},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair, tuple},
+    IResult,
+};
+
+type Regs = (u64, u64, u64);
+
+fn parse_input(input: &str) -> IResult<&str, (Regs, Vec<u8>)> {
+    all_consuming(separated_pair(
+        tuple((
+            delimited(tag("Register A: "), parse_unsigned, newline),
+            delimited(tag("Register B: "), parse_unsigned, newline),
+            delimited(tag("Register C: "), parse_unsigned, newline),
+        )),
+        newline,
+        delimited(
+            tag("Program: "),
+            separated_list1(char(','), parse_unsigned),
+            opt(newline),
+        ),
+    ))(input)
+}
+
+fn combo(operand: u8, regs: &Regs) -> Result<u64> {
+    match operand {
+        0..=3 => Ok(operand as u64),
+        4 => Ok(regs.0),
+        5 => Ok(regs.1),
+        6 => Ok(regs.2),
+        _ => bail!("Unexpected operand {}", operand),
+    }
+}
+
+#[derive(Clone, Default)]
+struct Computer {
+    regs: Regs,
+    ip: usize,
+    out: Vec<u8>,
+}
+
+impl Computer {
+    fn new(regs: Regs) -> Self {
+        Self {
+            regs,
+            ..Default::default()
+        }
+    }
+
+    fn halted(&self, instructions: &[u8]) -> bool {
+        self.ip + 1 >= instructions.len()
+    }
+
+    /// Executes the instruction at `ip`.
+    fn step(&mut self, instructions: &[u8]) -> Result<()> {
+        let regs = &mut self.regs;
+        let (instr, operand) = (instructions[self.ip], instructions[self.ip + 1]);
+        match instr {
+            0 => {
+                // adv
+                regs.0 /= 2u64.pow(combo(operand, regs)? as u32);
+                self.ip += 2;
+            }
+            1 => {
+                // bxl
+                regs.1 ^= operand as u64;
+                self.ip += 2;
+            }
+            2 => {
+                // bst
+                regs.1 = combo(operand, regs)? & 0b111;
+                self.ip += 2;
+            }
+            3 => {
+                // jnz
+                if regs.0 == 0 {
+                    self.ip += 2;
+                } else {
+                    self.ip = operand as usize;
+                }
+            }
+            4 => {
+                // bxc
+                regs.1 ^= regs.2;
+                self.ip += 2;
+            }
+            5 => {
+                // out
+                self.out.push((combo(operand, regs)? & 0b111) as u8);
+                self.ip += 2;
+            }
+            6 => {
+                // bdv
+                regs.1 = regs.0 / 2u64.pow(combo(operand, regs)? as u32);
+                self.ip += 2;
+            }
+            7 => {
+                // cdv
+                regs.2 = regs.0 / 2u64.pow(combo(operand, regs)? as u32);
+                self.ip += 2;
+            }
+            _ => bail!("Unexpected instruction {}", instr),
+        }
+        Ok(())
+    }
+}
+
+fn run_program(regs: Regs, instructions: &[u8], budget: &Budget) -> Result<Vec<u8>> {
+    let mut computer = Computer::new(regs);
+    while !computer.halted(instructions) {
+        budget.step()?;
+        computer.step(instructions)?;
+    }
+    Ok(computer.out)
+}
+
+/// Searches for the smallest value of register A that makes the program
+/// output itself.
+fn find_quine(regs: Regs, instructions: &[u8], budget: &Budget) -> Result<u64> {
+    (0..instructions.len())
+        .try_fold(vec![0], |acc, _| {
+            acc.into_iter()
+                .flat_map(|acc| (0..8).map(move |a| (acc << 3) | a))
+                .filter_map(
+                    |a| match run_program((a, regs.1, regs.2), instructions, budget) {
+                        Ok(out) => out
+                            .iter()
+                            .rev()
+                            .zip(instructions.iter().rev())
+                            .all(|(a, b)| a == b)
+                            .then_some(Ok(a)),
+                        Err(e) if e.is::<BudgetExceeded>() => Some(Err(e)),
+                        Err(_) => None,
+                    },
+                )
+                .collect::<Result<Vec<_>>>()
+        })?
+        .into_iter()
+        .min()
+        .context("No solution found")
+}
+
+fn format_output(out: &[u8]) -> String {
+    out.iter().map(|x| x.to_string()).join(",")
+}
+
+/// The computer part way through the program, one instruction per step.
+/// Setting a register or `ip` changes the running computer; the parts always
+/// start from the current registers.
+struct Debugger {
+    computer: Computer,
+    instructions: Vec<u8>,
+}
+
+pub fn repl(input: &str) -> Result<Box<dyn Session>> {
+    let (regs, instructions) = parse_input(input).map_err(|e| e.to_owned())?.1;
+    Ok(Box::new(Debugger {
+        computer: Computer::new(regs),
+        instructions,
+    }))
+}
+
+impl Session for Debugger {
+    fn show(&self) -> String {
+        let Computer { regs, ip, out } = &self.computer;
+        let next = match self.instructions.get(*ip..*ip + 2) {
+            Some([instr, operand]) => format!("{instr},{operand}"),
+            _ => "halted".to_owned(),
+        };
+        format!(
+            "A: {}\nB: {}\nC: {}\nip: {ip} ({next})\nout: {}\n",
+            regs.0,
+            regs.1,
+            regs.2,
+            format_output(out)
+        )
+    }
+
+    fn step(&mut self, n: usize) -> Result<()> {
+        for _ in 0..n {
+            if self.computer.halted(&self.instructions) {
+                bail!("The program has halted");
+            }
+            self.computer.step(&self.instructions)?;
+        }
+        Ok(())
+    }
+
+    fn part(&self, part: Part, budget: &Budget) -> Result<String> {
+        let regs = self.computer.regs;
+        match part {
+            Part::A => Ok(format_output(&run_program(
+                regs,
+                &self.instructions,
+                budget,
+            )?)),
+            Part::B => Ok(find_quine(regs, &self.instructions, budget)?.to_string()),
+        }
+    }
+
+    fn set(&mut self, param: &str, value: &str) -> Result<()> {
+        let value = value
+            .parse()
+            .with_context(|| format!("Invalid value for {param}: {value}"))?;
+        let regs = &mut self.computer.regs;
+        match param {
+            "a" | "A" => regs.0 = value,
+            "b" | "B" => regs.1 = value,
+            "c" | "C" => regs.2 = value,
+            "ip" => self.computer.ip = value as usize,
+            _ => bail!("No such parameter: {param}. Try a, b, c or ip"),
+        }
+        Ok(())
+    }
+}
+
+/// Checks the program has the shape `find_quine` relies on: a single loop that
+/// shifts three bits out of A and prints one number each time round.
+pub fn validate(input: &str) -> Result<Vec<Violation>> {
+    let instructions = parse_input(input).map_err(|e| e.to_owned())?.1 .1;
+    let mut violations = Vec::new();
+    if instructions.len() % 2 != 0 {
+        violations.push(Violation::new("The program has an odd number of values"));
+    }
+    let pairs = instructions.chunks_exact(2).collect_vec();
+    let count = |instruction: &[u8]| pairs.iter().filter(|pair| **pair == instruction).count();
+    if pairs.last() != Some(&&[3, 0][..]) {
+        violations.push(Violation::of_part(
+            Part::B,
+            "The program doesn't end by jumping back to the start",
+        ));
+    }
+    let jumps = pairs.iter().filter(|pair| pair[0] == 3).count();
+    if jumps > 1 {
+        violations.push(Violation::of_part(
+            Part::B,
+            format!("The program has {jumps} jumps rather than one"),
+        ));
+    }
+    if count(&[0, 3]) != 1 || pairs.iter().filter(|pair| pair[0] == 0).count() != 1 {
+        violations.push(Violation::of_part(
+            Part::B,
+            "A isn't divided by 8 exactly once per loop",
+        ));
+    }
+    let outputs = pairs.iter().filter(|pair| pair[0] == 5).count();
+    if outputs != 1 {
+        violations.push(Violation::of_part(
+            Part::B,
+            format!("The program outputs {outputs} times per loop rather than once"),
+        ));
+    }
+    Ok(violations)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let (regs, instructions) = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let budget = run.budget();
+
+    let part_a = format_output(&run_program(regs, &instructions, budget)?);
+    run.part_a(part_a);
+
+    if run.wants(Part::B) {
+        let part_b = find_quine(regs, &instructions, budget)?;
+        run.part_b(part_b);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anyhow::{Context, Ok, Result};
+use aoc::{
+    components::{self, Connectivity, UnionFind},
+    grid::{Grid, Pos},
+    parse::parse_unsigned,
+    render::{self, Image},
+    runner::{Part, Run, Violation},
+    search,
+};
+use nom::{
+    character::complete::{char, newline},
+    combinator::{all_consuming, opt},
+    multi::separated_list1,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tile {
+    Safe,
+    Corrupted,
+}
+
+type Map = Grid<Tile>;
+
+fn with_corruptions(corruptions: &[Pos]) -> Map {
+    corruptions
+        .iter()
+        .fold(Grid::filled(71, 71, Tile::Safe), |mut map, pos| {
+            if let Some(tile) = map.get_mut(*pos) {
+                *tile = Tile::Corrupted;
+            }
+            map
+        })
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Pos>> {
+    all_consuming(terminated(
+        separated_list1(
+            newline,
+            separated_pair(parse_unsigned, char(','), parse_unsigned),
+        ),
+        opt(newline),
+    ))(input)
+}
+
+fn manhattan_dist(a: Pos, b: Pos) -> u32 {
+    ((a.0 - b.0).abs() + (a.1 - b.1).abs()) as u32
+}
+
+fn find_path(start: Pos, end: Pos, map: &Map) -> Option<u32> {
+    search::astar(
+        start,
+        |&pos| pos == end,
+        |&pos| {
+            map.neighbours(pos)
+                .filter(|&next| map.get(next) == Some(&Tile::Safe))
+                .map(|next| (next, 1))
+        },
+        |&pos| manhattan_dist(pos, end),
+    )
+}
+
+/// The index of the first byte to cut the start off from the end, if any do.
+/// Works backwards from every byte having fallen, clearing them in reverse
+/// order and joining each cleared cell to the safe cells around it, until the
+/// start and end are joined.
+fn first_blocking_byte(corruptions: &[Pos], start: Pos, end: Pos) -> Option<usize> {
+    let mut falls = Grid::filled(71, 71, 0);
+    for &pos in corruptions {
+        if let Some(count) = falls.get_mut(pos) {
+            *count += 1;
+        }
+    }
+    let components = components::label(&falls, Connectivity::Four, |a, b| *a == 0 && *b == 0);
+    let label = |pos| components.labels.get(pos).copied();
+    let mut sets = UnionFind::new(components.len());
+    let (start, end) = (label(start)?, label(end)?);
+    if sets.connected(start, end) {
+        return None;
+    }
+
+    for (i, &pos) in corruptions.iter().enumerate().rev() {
+        // A cell that was hit more than once is only safe before its first hit.
+        let Some(count) = falls.get_mut(pos) else {
+            continue;
+        };
+        *count -= 1;
+        if *count > 0 {
+            continue;
+        }
+        for next in falls.neighbours(pos) {
+            if falls.get(next) == Some(&0) {
+                sets.union(label(pos)?, label(next)?);
+            }
+        }
+        if sets.connected(start, end) {
+            return Some(i);
+        }
+    }
+    None
+}
+
+/// Checks every byte lands in the 71x71 memory space, away from the start and
+/// end, and that there's at least a kilobyte of them for part A.
+pub fn validate(input: &str) -> Result<Vec<Violation>> {
+    let corruptions = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let map = Grid::filled(71, 71, ());
+    let mut violations = Vec::new();
+    if corruptions.len() < 1024 {
+        violations.push(Violation::of_part(
+            Part::A,
+            format!(
+                "Only {} bytes fall, fewer than the 1024 it needs",
+                corruptions.len()
+            ),
+        ));
+    }
+    for (i, &pos) in corruptions.iter().enumerate() {
+        if !map.contains(pos) {
+            violations.push(Violation::new(format!(
+                "Byte {} ({},{}) is outside the 71x71 memory space",
+                i + 1,
+                pos.0,
+                pos.1
+            )));
+        } else if pos == (0, 0) || pos == (70, 70) {
+            violations.push(Violation::new(format!(
+                "Byte {} ({},{}) falls on the start or end",
+                i + 1,
+                pos.0,
+                pos.1
+            )));
+        }
+    }
+    Ok(violations)
+}
+
+/// The memory space after the first kilobyte has fallen.
+pub fn render(input: &str) -> Result<Image> {
+    let corruptions = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let map = with_corruptions(&corruptions[..1024.min(corruptions.len())]);
+    Ok(render::draw_grid(&map, 4, |tile| {
+        (*tile == Tile::Corrupted).then_some(render::GREY)
+    }))
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let corruptions = parse_input(input).map_err(|e| e.to_owned())?.1;
+
+    if run.wants(Part::A) {
+        let map = with_corruptions(&corruptions[..1024.min(corruptions.len())]);
+        let part_a = find_path((0, 0), (70, 70), &map).context("No path found")?;
+        run.part_a(part_a);
+    }
+
+    let part_b = first_blocking_byte(&corruptions, (0, 0), (70, 70))
+        .context("Path found after all corruptions")
+        .map(|i| format!("{},{}", corruptions[i].0, corruptions[i].1))?;
+    run.part_b(part_b);
+
+    Ok(())
+}
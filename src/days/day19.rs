@@ -0,0 +1,60 @@
+use anyhow::Result;
+use aoc::{memo::Memo, runner::Run};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, newline},
+    combinator::{all_consuming, opt},
+    multi::separated_list1,
+    sequence::{pair, separated_pair, terminated},
+    IResult,
+};
+
+fn parse_input(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    all_consuming(terminated(
+        separated_pair(
+            separated_list1(tag(", "), alpha1),
+            pair(newline, newline),
+            separated_list1(newline, alpha1),
+        ),
+        opt(newline),
+    ))(input)
+}
+
+fn permutations<'a>(design: &'a str, towels: &[&str], cache: &mut Memo<&'a str, u64>) -> u64 {
+    cache.recurse(design, &|permutations, design| {
+        towels
+            .iter()
+            .map(|towel| {
+                if design == towel {
+                    1
+                } else if let Some(remainder) = design.strip_prefix(towel) {
+                    permutations(remainder)
+                } else {
+                    0
+                }
+            })
+            .sum()
+    })
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let (towels, designs) = parse_input(input).map_err(|e| e.to_owned())?.1;
+
+    let mut cache = Memo::new();
+    let perms = designs
+        .iter()
+        .map(|design| {
+            run.budget().step()?;
+            Ok(permutations(design, &towels, &mut cache))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    run.stat(format!("Cache: {}", cache.stats()));
+
+    let part_a = perms.iter().filter(|ways| **ways > 0).count();
+    run.part_a(part_a);
+
+    let part_b = perms.into_iter().sum::<u64>();
+    run.part_b(part_b);
+
+    Ok(())
+}
@@ -0,0 +1,342 @@
+use anyhow::{bail, ensure, Context, Result};
+use aoc::{
+    budget::Budget,
+    gridset::GridSet,
+    memo::Memo,
+    par::prelude::*,
+    runner::{Run, Violation},
+    search,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+type Pos = (isize, isize);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Track,
+    Wall,
+}
+
+struct Map {
+    width: isize,
+    height: isize,
+    tiles: Vec<Tile>,
+}
+
+impl Map {
+    fn get(&self, pos: Pos) -> Option<Tile> {
+        if (0..self.width).contains(&pos.0) && (0..self.height).contains(&pos.1) {
+            Some(self.tiles[(pos.1 * self.width + pos.0) as usize])
+        } else {
+            None
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Map, Pos, Pos)> {
+    let width = input.lines().next().context("Empty input")?.len() as isize;
+    let height = input.lines().count() as isize;
+    let (start, end, tiles) = input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(move |(x, c)| ((x as isize, y as isize), c))
+        })
+        .try_fold(
+            (None, None, Vec::new()),
+            |(mut start, mut end, mut tiles), (pos, c)| {
+                match c {
+                    '#' => {
+                        tiles.push(Tile::Wall);
+                    }
+                    'S' => {
+                        ensure!(start.is_none(), "Multiple starts found");
+                        tiles.push(Tile::Track);
+                        start = Some(pos);
+                    }
+                    'E' => {
+                        ensure!(end.is_none(), "Multiple ends found");
+                        tiles.push(Tile::Track);
+                        end = Some(pos);
+                    }
+                    '.' => {
+                        tiles.push(Tile::Track);
+                    }
+                    _ => bail!("Invalid character in map: {}", c),
+                }
+                Ok((start, end, tiles))
+            },
+        )?;
+    Ok((
+        Map {
+            width,
+            height,
+            tiles,
+        },
+        start.context("No start found")?,
+        end.context("No end found")?,
+    ))
+}
+
+type Cache = Memo<(Pos, Pos), usize>;
+
+fn find_path(
+    map: &Map,
+    start: Pos,
+    end: Pos,
+    cache: &mut Cache,
+    budget: &Budget,
+) -> Result<Option<usize>> {
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((0, vec![start])));
+    let mut closed = GridSet::new(map.width, map.height);
+
+    while let Some(Reverse((cost, path))) = open.pop() {
+        budget.step()?;
+        let pos = *path.last().unwrap();
+        if !closed.insert(pos) {
+            continue;
+        }
+
+        if pos == end {
+            for (i, pos) in path.iter().rev().enumerate() {
+                cache.insert((*pos, end), i);
+            }
+            return Ok(Some(path.len() - 1));
+        }
+
+        if let Some(remainder) = cache.get(&(pos, end)) {
+            for (i, pos) in path.iter().rev().enumerate() {
+                cache.insert((*pos, end), i + remainder);
+            }
+            return Ok(Some(path.len() - 1 + remainder));
+        }
+
+        open.extend(
+            [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .filter_map(|step| {
+                    let pos = (pos.0 + step.0, pos.1 + step.1);
+                    match map.get(pos) {
+                        Some(Tile::Track) => {
+                            let mut path = path.clone();
+                            path.push(pos);
+                            Some(Reverse((cost + 1, path)))
+                        }
+                        _ => None,
+                    }
+                }),
+        );
+    }
+    Ok(None)
+}
+
+fn find_cheat_starts(map: &Map, start: Pos) -> Vec<(Pos, usize)> {
+    let mut open = VecDeque::new();
+    open.push_back((0, start));
+    let mut closed = GridSet::new(map.width, map.height);
+
+    let mut cheat_starts = Vec::new();
+
+    while let Some((cost, pos)) = open.pop_front() {
+        if !closed.insert(pos) {
+            continue;
+        }
+        cheat_starts.push((pos, cost));
+
+        open.extend(
+            [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .filter_map(|step| {
+                    let pos = (pos.0 + step.0, pos.1 + step.1);
+                    match map.get(pos) {
+                        Some(Tile::Track) => Some((cost + 1, pos)),
+                        _ => None,
+                    }
+                }),
+        );
+    }
+
+    cheat_starts
+}
+
+fn find_cheat_ends(map: &Map, start: Pos, cheat_len: usize) -> Vec<(Pos, usize)> {
+    let mut open = VecDeque::new();
+    open.push_back((0, start, cheat_len));
+    let mut closed = GridSet::new(map.width, map.height);
+
+    let mut cheat_ends = Vec::new();
+
+    while let Some((cost, pos, remaining_len)) = open.pop_front() {
+        if !closed.insert(pos) {
+            continue;
+        }
+        if map.get(pos) == Some(Tile::Track) {
+            cheat_ends.push((pos, cost));
+        }
+
+        open.extend(
+            [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .filter_map(|step| {
+                    let pos = (pos.0 + step.0, pos.1 + step.1);
+                    // Any cheat that leaves the map has one as short that doesn't.
+                    (remaining_len > 0 && map.get(pos).is_some()).then_some((
+                        cost + 1,
+                        pos,
+                        remaining_len - 1,
+                    ))
+                }),
+        );
+    }
+
+    cheat_ends
+}
+
+fn find_cheat_paths(
+    cheat_starts: &[(Pos, usize)],
+    end: Pos,
+    cheat_len: usize,
+    max_len: usize,
+    map: &Map,
+    cache: &mut Cache,
+    budget: &Budget,
+) -> Result<usize> {
+    // Search from every cheat start in parallel, using only the distances
+    // already cached. Any cheat ends the cache doesn't cover are pathfound
+    // afterwards, once the cache can be updated again.
+    let (count, uncached) = cheat_starts
+        .maybe_par_iter()
+        .map(|(cheat_start, start_cost)| {
+            budget.step()?;
+            let mut count = 0;
+            let mut uncached = Vec::new();
+            for (cheat_end, cheat_cost) in find_cheat_ends(map, *cheat_start, cheat_len) {
+                let cost = start_cost + cheat_cost;
+                match cache.get(&(cheat_end, end)) {
+                    Some(remainder) => count += (cost + remainder <= max_len) as usize,
+                    None => uncached.push((cheat_end, cost)),
+                }
+            }
+            Ok((count, uncached))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .fold(
+            (0, Vec::new()),
+            |(count, mut uncached), (start_count, start_uncached)| {
+                uncached.extend(start_uncached);
+                (count + start_count, uncached)
+            },
+        );
+
+    let mut count = count;
+    for (cheat_end, cost) in uncached {
+        if find_path(map, cheat_end, end, cache, budget)?
+            .is_some_and(|remainder| cost + remainder <= max_len)
+        {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// The cheat search assumes the track is a single path from the start to the
+/// end: no branches, no dead ends, and no track off the path.
+pub fn validate(input: &str) -> Result<Vec<Violation>> {
+    let (map, start, end) = parse_input(input)?;
+    let neighbours = |pos: Pos| {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .map(move |step| (pos.0 + step.0, pos.1 + step.1))
+            .filter(|&next| map.get(next) == Some(Tile::Track))
+    };
+    let track = (0..map.height)
+        .flat_map(|y| (0..map.width).map(move |x| (x, y)))
+        .filter(|&pos| map.get(pos) == Some(Tile::Track))
+        .collect::<Vec<_>>();
+
+    let mut violations = Vec::new();
+    let mut report = |what: &str, positions: Vec<Pos>| {
+        if let Some(first) = positions.first() {
+            violations.push(Violation::new(format!(
+                "{} {what}, first at {first:?}",
+                positions.len()
+            )));
+        }
+    };
+    report(
+        "track positions branch",
+        track
+            .iter()
+            .copied()
+            .filter(|&pos| neighbours(pos).count() > 2)
+            .collect(),
+    );
+    report(
+        "dead ends away from the start and end",
+        track
+            .iter()
+            .copied()
+            .filter(|&pos| pos != start && pos != end && neighbours(pos).count() < 2)
+            .collect(),
+    );
+    let reachable = search::bfs(start, |&pos| neighbours(pos));
+    report(
+        "track positions can't be reached from the start",
+        track
+            .iter()
+            .copied()
+            .filter(|pos| !reachable.contains_key(pos))
+            .collect(),
+    );
+    for (name, pos) in [("start", start), ("end", end)] {
+        let count = neighbours(pos).count();
+        if count != 1 {
+            violations.push(Violation::new(format!(
+                "The {name} has {count} neighbouring track positions"
+            )));
+        }
+    }
+    Ok(violations)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let (map, start, end) = parse_input(input)?;
+    let mut cache = Memo::new();
+
+    let budget = run.budget();
+    let baseline =
+        find_path(&map, start, end, &mut cache, budget)?.context("Couldn't find path")?;
+    let cheat_starts = find_cheat_starts(&map, start);
+
+    let part_a = find_cheat_paths(
+        &cheat_starts,
+        end,
+        2,
+        baseline - 100,
+        &map,
+        &mut cache,
+        budget,
+    )?;
+    run.part_a(part_a);
+
+    let part_b = find_cheat_paths(
+        &cheat_starts,
+        end,
+        20,
+        baseline - 100,
+        &map,
+        &mut cache,
+        budget,
+    )?;
+    run.stat(format!("Cache: {}", cache.stats()));
+    run.part_b(part_b);
+
+    Ok(())
+}
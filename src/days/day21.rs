@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Context, Result};
+use aoc::{memo::Memo, runner::Run};
 use itertools::Itertools;
-use std::collections::HashMap;
 
 trait KeyPad {
     fn pos(&self) -> (i32, i32);
@@ -153,7 +153,7 @@ fn sequences_for_buttons<T: KeyPad>(a: T, b: T) -> Vec<Vec<DirPad>> {
     sequences
 }
 
-type Cache = HashMap<((i32, i32), (i32, i32), usize), usize>;
+type Cache = Memo<((i32, i32), (i32, i32), usize), usize>;
 
 fn num_dpad_presses_for_buttons<T: KeyPad + Copy>(
     a: T,
@@ -161,21 +161,17 @@ fn num_dpad_presses_for_buttons<T: KeyPad + Copy>(
     num_robots: usize,
     cache: &mut Cache,
 ) -> usize {
-    if let Some(presses) = cache.get(&(a.pos(), b.pos(), num_robots)) {
-        return *presses;
-    }
-
-    let sequences = sequences_for_buttons(a, b);
-    let presses = match num_robots {
-        0 => sequences.into_iter().map(|seq| seq.len()).min().unwrap(),
-        _ => sequences
-            .into_iter()
-            .map(|seq| num_dpad_presses_for_sequence(&seq, num_robots - 1, cache))
-            .min()
-            .unwrap(),
-    };
-    cache.insert((a.pos(), b.pos(), num_robots), presses);
-    presses
+    cache.get_or_insert_with((a.pos(), b.pos(), num_robots), |cache| {
+        let sequences = sequences_for_buttons(a, b);
+        match num_robots {
+            0 => sequences.into_iter().map(|seq| seq.len()).min().unwrap(),
+            _ => sequences
+                .into_iter()
+                .map(|seq| num_dpad_presses_for_sequence(&seq, num_robots - 1, cache))
+                .min()
+                .unwrap(),
+        }
+    })
 }
 
 fn num_dpad_presses_for_sequence<T: KeyPad + Copy>(
@@ -190,26 +186,26 @@ fn num_dpad_presses_for_sequence<T: KeyPad + Copy>(
         .sum()
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input21.txt");
+pub fn solve(input: &str, run: &Run) -> Result<()> {
     let codes = parse_input(input)?;
 
-    let mut cache = HashMap::default();
+    let mut cache = Memo::new();
     let part_a = codes
         .clone()
         .into_iter()
         .map(|(num, code)| num * num_dpad_presses_for_sequence(&code, 2, &mut cache))
         .sum::<usize>();
-    println!("Day 21, part A: {part_a}");
-    assert_eq!(part_a, 188398);
+    run.part_a(part_a);
+
+    run.stat(format!("Part A cache: {}", cache.stats()));
 
     cache.clear();
     let part_b = codes
         .into_iter()
         .map(|(num, code)| num * num_dpad_presses_for_sequence(&code, 25, &mut cache))
         .sum::<usize>();
-    println!("Day 21, part A: {part_b}");
-    assert_eq!(part_b, 230049027535970);
+    run.stat(format!("Part B cache: {}", cache.stats()));
+    run.part_b(part_b);
 
     Ok(())
 }
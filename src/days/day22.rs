@@ -1,5 +1,5 @@
 use anyhow::Result;
-use aoc2024::util::parse_unsigned;
+use aoc::{par::prelude::*, parse::parse_unsigned, runner::Run};
 use itertools::Itertools;
 use nom::{
     character::complete::newline,
@@ -17,14 +17,14 @@ fn parse_input(input: &str) -> IResult<&str, Vec<i64>> {
     ))(input)
 }
 
-fn main() -> Result<()> {
-    let input = include_str!("../../res/input22.txt");
-    let intial_numbers = parse_input(input)?.1;
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let intial_numbers = parse_input(input).map_err(|e| e.to_owned())?.1;
 
     let numbers_per_buyer = intial_numbers
-        .iter()
+        .maybe_par_iter()
         .map(|number| {
-            std::iter::successors(Some(*number), |n| {
+            run.budget().step()?;
+            Ok(std::iter::successors(Some(*number), |n| {
                 let mut n = *n;
                 n = (n ^ (n * 64)) % 16777216;
                 n = (n ^ (n / 32)) % 16777216;
@@ -32,21 +32,21 @@ fn main() -> Result<()> {
                 Some(n)
             })
             .take(2001)
-            .collect_vec()
+            .collect_vec())
         })
-        .collect_vec();
+        .collect::<Result<Vec<_>>>()?;
 
     let part_a = numbers_per_buyer
         .iter()
         .map(|numbers| numbers.last().unwrap())
         .sum::<i64>();
-    println!("Day 22, part A: {part_a}");
-    assert_eq!(part_a, 14119253575);
+    run.part_a(part_a);
 
     let price_per_buyer = numbers_per_buyer
-        .iter()
+        .maybe_par_iter()
         .map(|numbers| {
-            numbers
+            run.budget().step()?;
+            Ok(numbers
                 .windows(5)
                 .map(|window| {
                     (
@@ -65,22 +65,36 @@ fn main() -> Result<()> {
                         acc.entry(pattern).or_insert(price);
                         acc
                     },
-                )
+                ))
         })
-        .collect_vec();
+        .collect::<Result<Vec<_>>>()?;
 
-    let total_prices = price_per_buyer.into_iter().fold(
+    let total_prices = price_per_buyer.iter().fold(
         HashMap::default(),
         |mut acc: HashMap<[i64; 4], i64>, prices| {
             for (pattern, price) in prices {
-                *acc.entry(pattern).or_insert(0) += price;
+                *acc.entry(*pattern).or_insert(0) += price;
             }
             acc
         },
     );
-    let part_b = total_prices.into_values().max().unwrap();
-    println!("Day 22, part B: {part_b}");
-    assert_eq!(part_b, 1600);
+    let (pattern, part_b) = total_prices
+        .into_iter()
+        .max_by_key(|(_, total)| *total)
+        .unwrap();
+    run.part_b(part_b);
+
+    if run.explaining() {
+        let sellers = price_per_buyer
+            .iter()
+            .filter(|prices| prices.contains_key(&pattern))
+            .count();
+        run.explain(format!(
+            "Selling after the changes {} gets {part_b} bananas from {sellers} of the {} buyers",
+            pattern.iter().join(","),
+            price_per_buyer.len()
+        ));
+    }
 
     Ok(())
 }
@@ -0,0 +1,231 @@
+use anyhow::{ensure, Result};
+use aoc::{budget::Budget, components::UnionFind, runner::Run};
+use itertools::Itertools;
+use nom::{
+    character::complete::{alpha1, char, newline},
+    combinator::{all_consuming, opt},
+    multi::separated_list1,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+fn parse_input(input: &str) -> IResult<&str, Vec<(&str, &str)>> {
+    all_consuming(terminated(
+        separated_list1(newline, separated_pair(alpha1, char('-'), alpha1)),
+        opt(newline),
+    ))(input)
+}
+
+type Connections<'a> = HashMap<&'a str, HashSet<&'a str>>;
+
+/// Bron-Kerbosch with pivoting. Keeps only the largest clique found so far,
+/// rather than every clique, so memory stays proportional to the graph.
+fn find_max_clique<'a>(
+    connections: &Connections<'a>,
+    clique: &mut Vec<&'a str>,
+    mut candidates: HashSet<&'a str>,
+    mut excluded: HashSet<&'a str>,
+    best: &mut Vec<&'a str>,
+    budget: &Budget,
+) -> Result<()> {
+    budget.step()?;
+    let Some(pivot) = candidates
+        .union(&excluded)
+        .max_by_key(|node| connections.get(*node).unwrap().len())
+    else {
+        if clique.len() > best.len() {
+            best.clone_from(clique);
+        }
+        return Ok(());
+    };
+
+    let pivot_others = connections.get(pivot).unwrap();
+    let to_visit = candidates
+        .iter()
+        .filter(|node| !pivot_others.contains(*node))
+        .copied()
+        .collect_vec();
+    for node in to_visit {
+        let others = connections.get(node).unwrap();
+        clique.push(node);
+        find_max_clique(
+            connections,
+            clique,
+            candidates.intersection(others).copied().collect(),
+            excluded.intersection(others).copied().collect(),
+            best,
+            budget,
+        )?;
+        clique.pop();
+        candidates.remove(node);
+        excluded.insert(node);
+    }
+    Ok(())
+}
+
+/// Splits the computers into groups that are connected to each other, largest
+/// first. A clique never spans two groups.
+fn find_components<'a>(connections: &Connections<'a>) -> Vec<Vec<&'a str>> {
+    let nodes = connections.keys().copied().collect_vec();
+    let indices = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (*node, i))
+        .collect::<HashMap<_, _>>();
+    let mut sets = UnionFind::new(nodes.len());
+    for (node, others) in connections {
+        for other in others {
+            sets.union(indices[node], indices[other]);
+        }
+    }
+    let mut components = nodes
+        .iter()
+        .into_group_map_by(|node| sets.find(indices[*node]))
+        .into_values()
+        .map(|component| component.into_iter().copied().collect_vec())
+        .collect_vec();
+    components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+    components
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let connections = parse_input(input).map_err(|e| e.to_owned())?.1;
+    let budget = run.budget();
+    let connections =
+        connections
+            .into_iter()
+            .fold(HashMap::default(), |mut acc: Connections, (a, b)| {
+                acc.entry(a).or_default().insert(b);
+                acc.entry(b).or_default().insert(a);
+                acc
+            });
+
+    let part_a = connections
+        .iter()
+        .filter(|(a, _)| a.starts_with('t'))
+        .fold(
+            HashSet::default(),
+            |mut acc: HashSet<[&str; 3]>, (a, others)| {
+                for (b, c) in others.iter().tuple_combinations() {
+                    if connections.get(b).unwrap().contains(c) {
+                        let mut group = [*a, *b, *c];
+                        group.sort();
+                        acc.insert(group);
+                    }
+                }
+                acc
+            },
+        )
+        .len();
+    run.part_a(part_a);
+
+    let components = find_components(&connections);
+    let plural = if components.len() == 1 { "" } else { "s" };
+    run.explain(format!(
+        "{} computers in {} connected group{plural}, the largest of {}",
+        connections.len(),
+        components.len(),
+        components.first().map_or(0, |component| component.len())
+    ));
+
+    let mut max_clique = Vec::new();
+    for component in components {
+        // Components are largest first, so none of the rest can beat this.
+        if component.len() <= max_clique.len() {
+            break;
+        }
+        find_max_clique(
+            &connections,
+            &mut Vec::new(),
+            component.into_iter().collect(),
+            HashSet::default(),
+            &mut max_clique,
+            budget,
+        )?;
+    }
+    ensure!(!max_clique.is_empty(), "No groups found");
+    max_clique.sort();
+    let part_b = max_clique.join(",");
+    run.part_b(part_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn
+";
+
+    fn max_clique(input: &str) -> Vec<&str> {
+        let connections = parse_input(input).unwrap().1.into_iter().fold(
+            Connections::default(),
+            |mut acc, (a, b)| {
+                acc.entry(a).or_default().insert(b);
+                acc.entry(b).or_default().insert(a);
+                acc
+            },
+        );
+        let mut best = Vec::new();
+        find_max_clique(
+            &connections,
+            &mut Vec::new(),
+            connections.keys().copied().collect(),
+            HashSet::default(),
+            &mut best,
+            &Budget::unlimited(),
+        )
+        .unwrap();
+        best.sort();
+        best
+    }
+
+    #[test]
+    fn finds_example_clique() {
+        assert_eq!(max_clique(EXAMPLE), ["co", "de", "ka", "ta"]);
+    }
+
+    #[test]
+    fn keeps_one_of_two_equal_cliques() {
+        // Two triangles joined by a single edge, so neither is larger.
+        let clique = max_clique("aa-ab\nab-ac\nac-aa\nac-ba\nba-bb\nbb-bc\nbc-ba\n");
+        assert!(
+            clique == ["aa", "ab", "ac"] || clique == ["ba", "bb", "bc"],
+            "{clique:?}"
+        );
+    }
+}
@@ -0,0 +1,531 @@
+use anyhow::{bail, ensure, Context, Result};
+use aoc::{
+    budget::Budget,
+    runner::{Part, Run, Session},
+};
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, newline, one_of},
+    combinator::{all_consuming, map, opt, value},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair, terminated, tuple},
+    IResult,
+};
+use rustc_hash::FxHashMap as HashMap;
+use std::{fmt::Write as _, fs, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Op {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a & b,
+            Op::Or => a | b,
+            Op::Xor => a ^ b,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+        }
+    }
+}
+
+type Input<'a> = (Vec<(&'a str, bool)>, Vec<(&'a str, Op, &'a str, &'a str)>);
+
+fn parse_input(input: &str) -> IResult<&str, Input<'_>> {
+    all_consuming(terminated(
+        separated_pair(
+            separated_list1(
+                newline,
+                separated_pair(alphanumeric1, tag(": "), map(one_of("01"), |c| c == '1')),
+            ),
+            pair(newline, newline),
+            separated_list1(
+                newline,
+                map(
+                    tuple((
+                        alphanumeric1,
+                        delimited(
+                            char(' '),
+                            alt((
+                                value(Op::And, tag("AND")),
+                                value(Op::Or, tag("OR")),
+                                value(Op::Xor, tag("XOR")),
+                            )),
+                            char(' '),
+                        ),
+                        alphanumeric1,
+                        tag(" -> "),
+                        alphanumeric1,
+                    )),
+                    |(a, op, b, _, out)| (a, op, b, out),
+                ),
+            ),
+        ),
+        opt(newline),
+    ))(input)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Gate {
+    a: usize,
+    op: Op,
+    b: usize,
+    out: usize,
+}
+
+/// The wires and gates, with each wire numbered by the order it first appears.
+#[derive(Clone)]
+struct Circuit {
+    names: Vec<String>,
+    initial: Vec<Option<bool>>,
+    gates: Vec<Gate>,
+}
+
+impl Circuit {
+    fn new(input: &str) -> Result<Self> {
+        let (wires, gates) = parse_input(input).map_err(|e| e.to_owned())?.1;
+        let mut ids = HashMap::default();
+        let mut names = Vec::new();
+        let mut id = |name: &str| {
+            *ids.entry(name.to_owned()).or_insert_with(|| {
+                names.push(name.to_owned());
+                names.len() - 1
+            })
+        };
+        let wires = wires
+            .into_iter()
+            .map(|(name, value)| (id(name), value))
+            .collect_vec();
+        let gates = gates
+            .into_iter()
+            .map(|(a, op, b, out)| Gate {
+                a: id(a),
+                op,
+                b: id(b),
+                out: id(out),
+            })
+            .collect_vec();
+
+        let mut initial = vec![None; names.len()];
+        let mut driven = vec![false; names.len()];
+        for (wire, value) in wires {
+            ensure!(!driven[wire], "Wire {} is set twice", names[wire]);
+            driven[wire] = true;
+            initial[wire] = Some(value);
+        }
+        for gate in &gates {
+            ensure!(
+                !driven[gate.out],
+                "Wire {} is driven twice",
+                names[gate.out]
+            );
+            driven[gate.out] = true;
+        }
+        if let Some(wire) = (0..names.len()).find(|&wire| !driven[wire]) {
+            bail!("Wire {} is never set", names[wire]);
+        }
+        Ok(Self {
+            names,
+            initial,
+            gates,
+        })
+    }
+
+    /// The wires named `prefix` followed by a bit number, least significant
+    /// first.
+    fn bits(&self, prefix: char) -> Vec<usize> {
+        self.names
+            .iter()
+            .enumerate()
+            .filter_map(|(wire, name)| {
+                let bit = name.strip_prefix(prefix)?.parse::<u32>().ok()?;
+                Some((bit, wire))
+            })
+            .sorted()
+            .map(|(_, wire)| wire)
+            .collect()
+    }
+
+    /// The gates in an order where every gate comes after the gates driving
+    /// its inputs, or `None` if the gates form a loop.
+    fn order(&self) -> Option<Vec<usize>> {
+        let mut drivers = vec![None; self.names.len()];
+        for (i, gate) in self.gates.iter().enumerate() {
+            drivers[gate.out] = Some(i);
+        }
+        let mut users = vec![Vec::new(); self.gates.len()];
+        let mut waiting = vec![0; self.gates.len()];
+        for (i, gate) in self.gates.iter().enumerate() {
+            for input in [gate.a, gate.b] {
+                if let Some(driver) = drivers[input] {
+                    users[driver].push(i);
+                    waiting[i] += 1;
+                }
+            }
+        }
+        let mut ready = (0..self.gates.len())
+            .filter(|&i| waiting[i] == 0)
+            .collect_vec();
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(i) = ready.pop() {
+            order.push(i);
+            for &user in &users[i] {
+                waiting[user] -= 1;
+                if waiting[user] == 0 {
+                    ready.push(user);
+                }
+            }
+        }
+        (order.len() == self.gates.len()).then_some(order)
+    }
+
+    /// The value on every wire, given the values on the input wires.
+    fn evaluate(&self, order: &[usize], mut values: Vec<Option<bool>>) -> Vec<Option<bool>> {
+        for &i in order {
+            let gate = self.gates[i];
+            values[gate.out] = values[gate.a]
+                .zip(values[gate.b])
+                .map(|(a, b)| gate.op.apply(a, b));
+        }
+        values
+    }
+
+    fn number(values: &[Option<bool>], bits: &[usize]) -> Option<u64> {
+        bits.iter()
+            .rev()
+            .try_fold(0, |n, &wire| Some(n << 1 | values[wire]? as u64))
+    }
+
+    fn set_number(values: &mut [Option<bool>], bits: &[usize], n: u64) {
+        for (i, &wire) in bits.iter().enumerate() {
+            values[wire] = Some(n >> i & 1 == 1);
+        }
+    }
+
+    fn output(&self) -> Result<u64> {
+        let order = self.order().context("The gates form a loop")?;
+        let values = self.evaluate(&order, self.initial.clone());
+        Self::number(&values, &self.bits('z')).context("Some z wires have no value")
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        for gate in &mut self.gates {
+            if gate.out == a {
+                gate.out = b;
+            } else if gate.out == b {
+                gate.out = a;
+            }
+        }
+    }
+
+    /// Whether the circuit adds the x and y numbers into the z number, judged
+    /// by sums that exercise every bit and every carry.
+    fn is_adder(&self) -> bool {
+        let Some(order) = self.order() else {
+            return false;
+        };
+        let (xs, ys, zs) = (self.bits('x'), self.bits('y'), self.bits('z'));
+        let width = xs.len() as u32;
+        let mask = (1 << width) - 1;
+        (0..width)
+            .flat_map(|i| [(1 << i, 0), (0, 1 << i), (1 << i, 1 << i)])
+            .chain([(mask, 1), (1, mask), (mask, mask)])
+            .all(|(x, y)| {
+                let mut values = vec![None; self.names.len()];
+                Self::set_number(&mut values, &xs, x);
+                Self::set_number(&mut values, &ys, y);
+                let values = self.evaluate(&order, values);
+                Self::number(&values, &zs) == Some(x + y)
+            })
+    }
+
+    /// Gate outputs that break the pattern of a ripple-carry adder, where bit
+    /// `n` is `z = (x XOR y) XOR carry` and the next carry is
+    /// `(x AND y) OR ((x XOR y) AND carry)`.
+    fn misplaced_outputs(&self) -> Vec<usize> {
+        let is_input = |wire: usize| self.names[wire].starts_with(['x', 'y']);
+        let is_first = |wire: usize| self.names[wire][1..].parse::<u32>() == Ok(0);
+        let last_z = self.bits('z').last().copied();
+        let feeds = |wire: usize, op: Op| {
+            self.gates
+                .iter()
+                .any(|gate| gate.op == op && (gate.a == wire || gate.b == wire))
+        };
+        self.gates
+            .iter()
+            .filter(|gate| {
+                let from_inputs = is_input(gate.a) && is_input(gate.b);
+                let first_bit = from_inputs && is_first(gate.a);
+                let to_z = self.names[gate.out].starts_with('z');
+                match gate.op {
+                    // Only the final carry reaches a z wire without an XOR.
+                    _ if to_z && gate.op != Op::Xor => Some(gate.out) != last_z,
+                    Op::Xor if !from_inputs => !to_z,
+                    Op::Xor => !first_bit && !feeds(gate.out, Op::Xor),
+                    Op::And => !first_bit && !feeds(gate.out, Op::Or),
+                    Op::Or => false,
+                }
+            })
+            .map(|gate| gate.out)
+            .collect()
+    }
+
+    /// The pairs of swapped outputs, found by pairing up the misplaced outputs
+    /// until the circuit adds correctly.
+    fn find_swaps(&self, budget: &Budget) -> Result<Vec<(usize, usize)>> {
+        fn pairings(wires: &[usize]) -> Vec<Vec<(usize, usize)>> {
+            let Some((&first, rest)) = wires.split_first() else {
+                return vec![Vec::new()];
+            };
+            (0..rest.len())
+                .flat_map(|i| {
+                    let mut others = rest.to_vec();
+                    let partner = others.remove(i);
+                    pairings(&others).into_iter().map(move |mut pairs| {
+                        pairs.push((first, partner));
+                        pairs
+                    })
+                })
+                .collect()
+        }
+
+        let misplaced = self.misplaced_outputs();
+        ensure!(
+            misplaced.len() == 8,
+            "Expected 8 misplaced outputs, found {}: {}",
+            misplaced.len(),
+            misplaced.iter().map(|&wire| &self.names[wire]).join(",")
+        );
+        for pairs in pairings(&misplaced) {
+            budget.step()?;
+            let mut circuit = self.clone();
+            for &(a, b) in &pairs {
+                circuit.swap(a, b);
+            }
+            if circuit.is_adder() {
+                return Ok(pairs);
+            }
+        }
+        bail!("No pairing of the misplaced outputs makes an adder")
+    }
+
+    /// The gate graph in Graphviz's DOT language, with each wire labelled by
+    /// its value.
+    fn to_dot(&self) -> String {
+        let values = self
+            .order()
+            .map(|order| self.evaluate(&order, self.initial.clone()));
+        let mut dot = String::from("digraph circuit {\n");
+        for (wire, name) in self.names.iter().enumerate() {
+            let value = match values.as_ref().and_then(|values| values[wire]) {
+                Some(value) => (value as u8).to_string(),
+                None => "?".to_owned(),
+            };
+            writeln!(dot, "  {name} [label=\"{name}\\n{value}\"];").unwrap();
+        }
+        for (i, gate) in self.gates.iter().enumerate() {
+            writeln!(dot, "  gate{i} [shape=box, label=\"{}\"];", gate.op.name()).unwrap();
+            writeln!(
+                dot,
+                "  {} -> gate{i};\n  {} -> gate{i};\n  gate{i} -> {};",
+                self.names[gate.a], self.names[gate.b], self.names[gate.out]
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+pub fn repl(input: &str) -> Result<Box<dyn Session>> {
+    Ok(Box::new(Circuit::new(input)?))
+}
+
+/// `set x N` and `set y N` change the numbers being added, and `render` saves
+/// the gate graph as a DOT file.
+impl Session for Circuit {
+    fn show(&self) -> String {
+        let (xs, ys) = (self.bits('x'), self.bits('y'));
+        let x = Circuit::number(&self.initial, &xs);
+        let y = Circuit::number(&self.initial, &ys);
+        let show = |n: Option<u64>| n.map_or_else(|| "?".to_owned(), |n| n.to_string());
+        let z = self
+            .output()
+            .map_or_else(|e| format!("{e}"), |z| z.to_string());
+        format!(
+            "{} wires, {} gates\nx = {}\ny = {}\nz = {z}\nx + y = {}\n",
+            self.names.len(),
+            self.gates.len(),
+            show(x),
+            show(y),
+            show(x.zip(y).map(|(x, y)| x + y))
+        )
+    }
+
+    fn part(&self, part: Part, budget: &Budget) -> Result<String> {
+        match part {
+            Part::A => Ok(self.output()?.to_string()),
+            Part::B => Ok(self
+                .find_swaps(budget)?
+                .into_iter()
+                .flat_map(|(a, b)| [&self.names[a], &self.names[b]])
+                .sorted()
+                .join(",")),
+        }
+    }
+
+    fn set(&mut self, param: &str, value: &str) -> Result<()> {
+        let prefix = match param {
+            "x" => 'x',
+            "y" => 'y',
+            _ => bail!("No such parameter: {param}. Try x or y"),
+        };
+        let n = value
+            .parse::<u64>()
+            .with_context(|| format!("Invalid value for {param}: {value}"))?;
+        let bits = self.bits(prefix);
+        ensure!(
+            bits.len() >= 64 || n >> bits.len() == 0,
+            "{n} doesn't fit in {} bits",
+            bits.len()
+        );
+        Self::set_number(&mut self.initial, &bits, n);
+        Ok(())
+    }
+
+    fn render(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_dot())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let circuit = Circuit::new(input)?;
+
+    let part_a = circuit.output()?;
+    run.part_a(part_a);
+
+    // Only an adder has one more z bit than x bits, which rules out the
+    // examples.
+    if circuit.bits('z').len() != circuit.bits('x').len() + 1 {
+        return Ok(());
+    }
+    let swaps = circuit.find_swaps(run.budget())?;
+    for &(a, b) in &swaps {
+        run.explain(format!(
+            "Swapped {} and {}",
+            circuit.names[a], circuit.names[b]
+        ));
+    }
+    let part_b = swaps
+        .into_iter()
+        .flat_map(|(a, b)| [&circuit.names[a], &circuit.names[b]])
+        .sorted()
+        .join(",");
+    run.part_b(part_b);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BITS: usize = 45;
+
+    /// A ripple-carry adder over `BITS` bits, with each pair in `swaps`
+    /// having its outputs exchanged.
+    fn adder(x: u64, y: u64, swaps: &[(&str, &str)]) -> String {
+        let mut gates = Vec::new();
+        for i in 0..BITS {
+            let (x, y, z) = (format!("x{i:02}"), format!("y{i:02}"), format!("z{i:02}"));
+            if i == 0 {
+                gates.push((x.clone(), "XOR", y.clone(), z));
+                gates.push((x, "AND", y, "c00".to_owned()));
+                continue;
+            }
+            let (sum, carry) = (format!("s{i:02}"), format!("c{:02}", i - 1));
+            let carry_out = if i == BITS - 1 {
+                format!("z{BITS:02}")
+            } else {
+                format!("c{i:02}")
+            };
+            gates.push((x.clone(), "XOR", y.clone(), sum.clone()));
+            gates.push((sum.clone(), "XOR", carry.clone(), z));
+            gates.push((x, "AND", y, format!("a{i:02}")));
+            gates.push((sum, "AND", carry, format!("b{i:02}")));
+            gates.push((format!("a{i:02}"), "OR", format!("b{i:02}"), carry_out));
+        }
+        for (a, b) in swaps {
+            for gate in &mut gates {
+                if gate.3 == *a {
+                    gate.3 = b.to_string();
+                } else if gate.3 == *b {
+                    gate.3 = a.to_string();
+                }
+            }
+        }
+
+        let mut text = String::new();
+        for (prefix, n) in [('x', x), ('y', y)] {
+            for i in 0..BITS {
+                writeln!(text, "{prefix}{i:02}: {}", n >> i & 1).unwrap();
+            }
+        }
+        text.push('\n');
+        for (a, op, b, out) in gates {
+            writeln!(text, "{a} {op} {b} -> {out}").unwrap();
+        }
+        text
+    }
+
+    #[test]
+    fn unswapped_adder_adds() {
+        let (x, y) = (0x1234_5678_9abc, 0x0fed_cba9_8765);
+        let circuit = Circuit::new(&adder(x, y, &[])).unwrap();
+        assert!(circuit.is_adder());
+        assert!(circuit.misplaced_outputs().is_empty());
+        assert_eq!(circuit.output().unwrap(), x + y);
+    }
+
+    #[test]
+    fn finds_swapped_outputs() {
+        // One of each kind of swap seen in puzzle inputs: a z with the carry
+        // out, with the AND of the sum and carry in, with the AND of its
+        // inputs, and an input XOR with the matching input AND.
+        let swaps = [
+            ("z10", "c10"),
+            ("z20", "b20"),
+            ("s30", "a30"),
+            ("z35", "a35"),
+        ];
+        let circuit = Circuit::new(&adder(123456789, 987654321, &swaps)).unwrap();
+        assert!(!circuit.is_adder());
+
+        let found = circuit.find_swaps(&Budget::default()).unwrap();
+        let names = found
+            .into_iter()
+            .flat_map(|(a, b)| [&circuit.names[a], &circuit.names[b]])
+            .sorted()
+            .join(",");
+        assert_eq!(names, "a30,a35,b20,c10,s30,z10,z20,z35");
+    }
+
+    #[test]
+    fn rejects_wires_driven_twice() {
+        let input = "x00: 1\ny00: 0\n\nx00 AND y00 -> z00\nx00 OR y00 -> z00\n";
+        assert!(Circuit::new(input).is_err());
+    }
+}
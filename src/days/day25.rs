@@ -0,0 +1,143 @@
+use anyhow::{bail, ensure, Context, Result};
+use aoc::{grid::Grid, runner::Run};
+use itertools::Itertools;
+
+const WIDTH: isize = 5;
+const HEIGHT: isize = 7;
+
+/// How far each column's pins reach into the space between the top and bottom
+/// rows.
+type Heights = [usize; WIDTH as usize];
+
+struct Schematics {
+    locks: Vec<Heights>,
+    keys: Vec<Heights>,
+}
+
+fn parse_schematic(block: &str) -> Result<(bool, Heights)> {
+    let grid = Grid::parse(block, |_, c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => bail!("Invalid input: {}", c),
+    })?;
+    ensure!(
+        grid.width() == WIDTH && grid.height() == HEIGHT,
+        "Schematic is {}x{}, not {WIDTH}x{HEIGHT}",
+        grid.width(),
+        grid.height()
+    );
+
+    let row_filled = |y| (0..WIDTH).all(|x| grid.get((x, y)) == Some(&true));
+    let is_lock = row_filled(0);
+    ensure!(
+        is_lock != row_filled(HEIGHT - 1),
+        "Schematic isn't a lock or a key"
+    );
+
+    let mut heights = [0; WIDTH as usize];
+    for (x, height) in heights.iter_mut().enumerate() {
+        let column = (0..HEIGHT)
+            .map(|y| grid.get((x as isize, y)) == Some(&true))
+            .collect_vec();
+        // Locks' pins hang down from the top, keys' stick up from the bottom.
+        let filled = if is_lock {
+            column.iter().take_while(|filled| **filled).count()
+        } else {
+            column.iter().rev().take_while(|filled| **filled).count()
+        };
+        ensure!(
+            column.iter().filter(|filled| **filled).count() == filled,
+            "Column {} has a gap",
+            x + 1
+        );
+        *height = filled - 1;
+    }
+    Ok((is_lock, heights))
+}
+
+fn parse_input(input: &str) -> Result<Schematics> {
+    let mut schematics = Schematics {
+        locks: Vec::new(),
+        keys: Vec::new(),
+    };
+    for (i, block) in input.trim_end().split("\n\n").enumerate() {
+        let (is_lock, heights) =
+            parse_schematic(block).with_context(|| format!("Schematic {}", i + 1))?;
+        if is_lock {
+            schematics.locks.push(heights);
+        } else {
+            schematics.keys.push(heights);
+        }
+    }
+    Ok(schematics)
+}
+
+fn fits(lock: &Heights, key: &Heights) -> bool {
+    lock.iter()
+        .zip(key)
+        .all(|(lock, key)| lock + key <= HEIGHT as usize - 2)
+}
+
+pub fn solve(input: &str, run: &Run) -> Result<()> {
+    let schematics = parse_input(input)?;
+    run.explain(format!(
+        "{} locks and {} keys",
+        schematics.locks.len(),
+        schematics.keys.len()
+    ));
+
+    let part_a = schematics
+        .locks
+        .iter()
+        .cartesian_product(&schematics.keys)
+        .filter(|(lock, key)| fits(lock, key))
+        .count();
+    run.part_a(part_a);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(block: &str) -> String {
+        parse_schematic(block).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn parses_locks_and_keys() {
+        let lock = "#####\n.####\n.####\n.####\n.#.#.\n.#...\n.....";
+        assert_eq!(parse_schematic(lock).unwrap(), (true, [0, 5, 3, 4, 3]));
+        let key = ".....\n#....\n#....\n#...#\n#.#.#\n#.###\n#####";
+        assert_eq!(parse_schematic(key).unwrap(), (false, [5, 0, 2, 1, 3]));
+    }
+
+    #[test]
+    fn rejects_wrong_size() {
+        assert_eq!(
+            error("####\n.###\n....\n....\n....\n....\n...."),
+            "Schematic is 4x7, not 5x7"
+        );
+        assert_eq!(
+            error("#####\n.....\n.....\n#####"),
+            "Schematic is 5x4, not 5x7"
+        );
+    }
+
+    #[test]
+    fn rejects_neither_lock_nor_key() {
+        let both = "#####\n.....\n.....\n.....\n.....\n.....\n#####";
+        assert_eq!(error(both), "Schematic isn't a lock or a key");
+        let neither = ".....\n.....\n.....\n.....\n.....\n.....\n.....";
+        assert_eq!(error(neither), "Schematic isn't a lock or a key");
+    }
+
+    #[test]
+    fn rejects_gaps() {
+        let lock = "#####\n#####\n.#.##\n.##..\n.....\n.....\n.....";
+        assert_eq!(error(lock), "Column 3 has a gap");
+        let key = ".....\n#....\n.....\n#....\n#....\n#....\n#####";
+        assert_eq!(error(key), "Column 1 has a gap");
+    }
+}
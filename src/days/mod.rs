@@ -0,0 +1,69 @@
+use aoc::runner::Day;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub const DAYS: &[Day] = &[
+    Day::new(1, day01::solve)
+        .with_repl(day01::repl)
+        .with_stream(day01::stream),
+    Day::new(2, day02::solve),
+    Day::new(3, day03::solve),
+    Day::new(4, day04::solve),
+    Day::new(5, day05::solve),
+    Day::new(6, day06::solve)
+        .with_render(day06::render)
+        .with_trace(day06::trace),
+    Day::new(7, day07::solve),
+    Day::new(8, day08::solve),
+    Day::new(9, day09::solve),
+    Day::new(10, day10::solve),
+    Day::new(11, day11::solve),
+    Day::new(12, day12::solve).with_render(day12::render),
+    Day::new(13, day13::solve).with_validate(day13::validate),
+    Day::new(14, day14::solve)
+        .with_render(day14::render)
+        .with_validate(day14::validate),
+    Day::new(15, day15::solve)
+        .with_repl(day15::repl)
+        .with_render(day15::render)
+        .with_validate(day15::validate)
+        .with_trace(day15::trace),
+    Day::new(16, day16::solve),
+    Day::new(17, day17::solve)
+        .with_repl(day17::repl)
+        .with_validate(day17::validate),
+    Day::new(18, day18::solve)
+        .with_render(day18::render)
+        .with_validate(day18::validate),
+    Day::new(19, day19::solve),
+    Day::new(20, day20::solve).with_validate(day20::validate),
+    Day::new(21, day21::solve),
+    Day::new(22, day22::solve),
+    Day::new(23, day23::solve),
+    Day::new(24, day24::solve).with_repl(day24::repl),
+    Day::new(25, day25::solve),
+];
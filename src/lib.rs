@@ -1 +1 @@
-pub mod util;
+pub mod days;
@@ -0,0 +1,11 @@
+use anyhow::Result;
+use aoc::runner::{self, Year};
+use aoc2024::days::DAYS;
+
+fn main() -> Result<()> {
+    runner::main(&Year {
+        name: "aoc2024",
+        root: env!("CARGO_MANIFEST_DIR"),
+        days: DAYS,
+    })
+}